@@ -0,0 +1,32 @@
+use crate::{errors::LRTPoolError, events::AdminTransferred, state::LRTPool};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump,
+        constraint = pool.pending_admin == Some(new_admin.key())
+            @ LRTPoolError::InvalidPendingAdmin
+    )]
+    pool: Account<'info, LRTPool>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        let old_admin = self.pool.admin;
+        self.pool.admin = self.new_admin.key();
+        self.pool.pending_admin = None;
+
+        emit!(AdminTransferred {
+            pool: self.pool.key(),
+            old_admin,
+            new_admin: self.pool.admin,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
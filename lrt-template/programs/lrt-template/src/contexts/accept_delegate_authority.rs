@@ -0,0 +1,32 @@
+use crate::{errors::LRTPoolError, events::DelegateAuthorityTransferred, state::LRTPool};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptDelegateAuthority<'info> {
+    new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump,
+        constraint = pool.pending_delegate_authority == Some(new_authority.key())
+            @ LRTPoolError::InvalidPendingDelegateAuthority
+    )]
+    pool: Account<'info, LRTPool>,
+}
+
+impl<'info> AcceptDelegateAuthority<'info> {
+    pub fn accept_authority(&mut self) -> Result<()> {
+        let old_authority = self.pool.delegate_authority;
+        self.pool.delegate_authority = self.new_authority.key();
+        self.pool.pending_delegate_authority = None;
+
+        emit!(DelegateAuthorityTransferred {
+            pool: self.pool.key(),
+            old_authority,
+            new_authority: self.pool.delegate_authority,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
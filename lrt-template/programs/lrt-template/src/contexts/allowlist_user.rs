@@ -0,0 +1,80 @@
+use crate::{
+    events::{AllowlistEntryAdded, AllowlistEntryRemoved},
+    state::*,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct AllowlistUser<'info> {
+    #[account(mut)]
+    admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump,
+        constraint = pool.admin == admin.key()
+    )]
+    pool: Account<'info, LRTPool>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AllowlistEntry::INIT_SPACE,
+        seeds = [b"allowlist", pool.key().as_ref(), user.as_ref()],
+        bump
+    )]
+    allowlist_entry: Account<'info, AllowlistEntry>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> AllowlistUser<'info> {
+    pub fn add_to_allowlist(&mut self, user: Pubkey, bumps: AllowlistUserBumps) -> Result<()> {
+        self.allowlist_entry.set_inner(AllowlistEntry {
+            bump: bumps.allowlist_entry,
+            pool: self.pool.key(),
+            user,
+        });
+
+        emit!(AllowlistEntryAdded {
+            pool: self.pool.key(),
+            user,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RemoveAllowlistUser<'info> {
+    #[account(mut)]
+    admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump,
+        constraint = pool.admin == admin.key()
+    )]
+    pool: Account<'info, LRTPool>,
+
+    #[account(
+        mut,
+        close = admin,
+        has_one = pool,
+        seeds = [b"allowlist", pool.key().as_ref(), allowlist_entry.user.as_ref()],
+        bump = allowlist_entry.bump
+    )]
+    allowlist_entry: Account<'info, AllowlistEntry>,
+}
+
+impl<'info> RemoveAllowlistUser<'info> {
+    // the allowlist entry is closed by the account constraints
+    pub fn remove_from_allowlist(&mut self) -> Result<()> {
+        emit!(AllowlistEntryRemoved {
+            pool: self.pool.key(),
+            user: self.allowlist_entry.user,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{errors::LRTPoolError, events::TransactionApproved, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ApproveTransaction<'info> {
+    owner: Signer<'info>,
+    #[account(
+        seeds = [b"multisig", multisig.create_key.as_ref()],
+        bump = multisig.bump
+    )]
+    multisig: Account<'info, Multisig>,
+    #[account(
+        mut,
+        has_one = multisig,
+        seeds = [
+            b"multisig_proposal",
+            multisig.key().as_ref(),
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    proposal: Account<'info, MultisigProposal>,
+}
+
+impl<'info> ApproveTransaction<'info> {
+    pub fn approve_transaction(&mut self) -> Result<()> {
+        if self.proposal.owner_set_seqno != self.multisig.owner_set_seqno {
+            return Err(LRTPoolError::StaleMultisigProposal.into());
+        }
+        if self.proposal.executed {
+            return Err(LRTPoolError::MultisigProposalAlreadyExecuted.into());
+        }
+
+        let owner_index = self
+            .multisig
+            .owner_index(&self.owner.key())
+            .ok_or(LRTPoolError::NotMultisigOwner)?;
+        self.proposal.approvals[owner_index] = true;
+
+        emit!(TransactionApproved {
+            multisig: self.multisig.key(),
+            proposal: self.proposal.key(),
+            owner: self.owner.key(),
+        });
+        Ok(())
+    }
+}
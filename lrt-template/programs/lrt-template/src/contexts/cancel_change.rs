@@ -0,0 +1,41 @@
+use crate::{events::ChangeCancelled, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelChange<'info> {
+    #[account(
+        constraint = authority.key() == pool.guardian || authority.key() == pool.admin
+    )]
+    authority: Signer<'info>,
+    // receives the rent the admin paid when queueing the change
+    #[account(mut, address = pool.admin)]
+    admin: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Account<'info, LRTPool>,
+
+    #[account(
+        mut,
+        close = admin,
+        has_one = pool,
+        seeds = [b"pending_change", pool.key().as_ref(), pending_change.id.to_le_bytes().as_ref()],
+        bump = pending_change.bump
+    )]
+    pending_change: Account<'info, PendingChange>,
+}
+
+impl<'info> CancelChange<'info> {
+    pub fn cancel_change(&mut self) -> Result<()> {
+        // the pending change account is closed by the account constraints
+        emit!(ChangeCancelled {
+            pool: self.pool.key(),
+            pending_change: self.pending_change.key(),
+            cancelled_by: self.authority.key(),
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{events::DelegateAuthorityProposalCancelled, state::LRTPool};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelDelegateAuthority<'info> {
+    #[account(mut)]
+    admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump,
+        constraint = pool.admin == admin.key()
+    )]
+    pool: Account<'info, LRTPool>,
+}
+
+impl<'info> CancelDelegateAuthority<'info> {
+    // new delegate authorities are proposed through ConfigChange::DelegateAuthority
+    pub fn cancel_proposal(&mut self) -> Result<()> {
+        let proposed_authority = self.pool.pending_delegate_authority.take();
+
+        emit!(DelegateAuthorityProposalCancelled {
+            pool: self.pool.key(),
+            proposed_authority,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
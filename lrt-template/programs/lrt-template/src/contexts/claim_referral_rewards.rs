@@ -0,0 +1,90 @@
+use crate::{errors::LRTPoolError, events::ReferralRewardsClaimed, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    referrer: Signer<'info>,
+
+    #[account(
+        mint::token_program = input_token_program,
+        address = pool.input_token_mint
+    )]
+    input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::authority = pool,
+        associated_token::mint = input_token_mint,
+        associated_token::token_program = input_token_program
+    )]
+    pool_input_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = input_token_mint,
+        token::token_program = input_token_program
+    )]
+    referrer_input_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = input_token_mint,
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    #[account(
+        mut,
+        has_one = pool,
+        has_one = referrer,
+        seeds = [b"referrer_rewards", pool.key().as_ref(), referrer.key().as_ref()],
+        bump = referrer_rewards.bump
+    )]
+    referrer_rewards: Account<'info, ReferrerRewards>,
+    input_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimReferralRewards<'info> {
+    pub fn claim_referral_rewards(&mut self) -> Result<()> {
+        let amount = self.referrer_rewards.unclaimed_amount;
+        if self.pool_input_token_vault.amount < amount {
+            return Err(LRTPoolError::InsufficientStakedSOLFundsForWithdraw.into());
+        }
+
+        let bump = [self.pool.bump];
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"lrt_pool", self.pool.output_token_mint.as_ref(), &bump][..]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.input_token_program.to_account_info(),
+            TransferChecked {
+                from: self.pool_input_token_vault.to_account_info(),
+                to: self.referrer_input_token_vault.to_account_info(),
+                mint: self.input_token_mint.to_account_info(),
+                authority: self.pool.to_account_info(),
+            },
+            &signer_seeds,
+        );
+        transfer_checked(ctx, amount, self.input_token_mint.decimals)?;
+
+        self.referrer_rewards.unclaimed_amount = 0;
+        self.referrer_rewards.total_claimed = self
+            .referrer_rewards
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        self.pool.unclaimed_referral_rewards = self
+            .pool
+            .unclaimed_referral_rewards
+            .checked_sub(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+
+        emit!(ReferralRewardsClaimed {
+            pool: self.pool.key(),
+            referrer: self.referrer.key(),
+            amount,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{errors::LRTPoolError, events::MultisigCreated, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    // any fresh keypair, it only makes the multisig address unique
+    create_key: Signer<'info>,
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Multisig::INIT_SPACE,
+        seeds = [b"multisig", create_key.key().as_ref()],
+        bump
+    )]
+    multisig: Account<'info, Multisig>,
+    #[account(
+        seeds = [b"multisig_signer", multisig.key().as_ref()],
+        bump
+    )]
+    multisig_signer: SystemAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> CreateMultisig<'info> {
+    pub fn create_multisig(
+        &mut self,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+        bumps: CreateMultisigBumps,
+    ) -> Result<()> {
+        validate_owners(&owners, threshold)?;
+
+        self.multisig.set_inner(Multisig {
+            bump: bumps.multisig,
+            signer_bump: bumps.multisig_signer,
+            create_key: self.create_key.key(),
+            owners,
+            threshold,
+            proposal_count: 0,
+            owner_set_seqno: 0,
+        });
+
+        emit!(MultisigCreated {
+            multisig: self.multisig.key(),
+            owners: self.multisig.owners.clone(),
+            threshold,
+        });
+        Ok(())
+    }
+}
+
+pub fn validate_owners(owners: &[Pubkey], threshold: u8) -> Result<()> {
+    if owners.is_empty() || owners.len() > MAX_MULTISIG_OWNERS {
+        return Err(LRTPoolError::InvalidMultisigOwners.into());
+    }
+    for (i, owner) in owners.iter().enumerate() {
+        if owners[..i].contains(owner) {
+            return Err(LRTPoolError::InvalidMultisigOwners.into());
+        }
+    }
+    if threshold == 0 || threshold as usize > owners.len() {
+        return Err(LRTPoolError::InvalidMultisigThreshold.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owners(len: usize) -> Vec<Pubkey> {
+        (0..len).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    #[test]
+    fn owners_must_be_unique_and_bounded() {
+        assert!(validate_owners(&owners(1), 1).is_ok());
+        assert!(validate_owners(&owners(MAX_MULTISIG_OWNERS), 3).is_ok());
+
+        let expected = Err(LRTPoolError::InvalidMultisigOwners.into());
+        assert_eq!(validate_owners(&[], 1), expected);
+        assert_eq!(
+            validate_owners(&owners(MAX_MULTISIG_OWNERS + 1), 1),
+            expected
+        );
+        let mut duplicated = owners(3);
+        duplicated[2] = duplicated[0];
+        assert_eq!(validate_owners(&duplicated, 2), expected);
+    }
+
+    #[test]
+    fn threshold_must_be_reachable() {
+        let expected = Err(LRTPoolError::InvalidMultisigThreshold.into());
+        assert_eq!(validate_owners(&owners(3), 0), expected);
+        assert_eq!(validate_owners(&owners(3), 4), expected);
+        assert!(validate_owners(&owners(3), 3).is_ok());
+    }
+}
@@ -5,7 +5,12 @@ use anchor_spl::{
 };
 use solana_program::{instruction::Instruction, program::invoke_signed};
 
-use crate::{errors::LRTPoolError, state::LRTPool, utils::sighash};
+use crate::{
+    errors::LRTPoolError,
+    events::{Delegated, Undelegated},
+    state::{AvsAllowlist, AvsPoints, LRTPool},
+    utils::sighash,
+};
 
 #[derive(Accounts)]
 pub struct Delegate<'info> {
@@ -56,7 +61,28 @@ pub struct Delegate<'info> {
         constraint = pool.delegate_authority == signer.key()
     )]
     pool: Account<'info, LRTPool>,
+    #[account(address = pool.output_token_mint)]
+    output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + AvsPoints::INIT_SPACE,
+        seeds = [b"avs_points", pool.key().as_ref(), avs.key().as_ref()],
+        bump
+    )]
+    avs_points: Box<Account<'info, AvsPoints>>,
     avs_program: AccountInfo<'info>,
+    // only an avs added through a timelocked AddAvs change can receive the pool's signature
+    #[account(
+        seeds = [
+            b"avs_allowlist",
+            pool.key().as_ref(),
+            avs.key().as_ref(),
+            avs_program.key().as_ref()
+        ],
+        bump = avs_allowlist.bump
+    )]
+    avs_allowlist: Box<Account<'info, AvsAllowlist>>,
     token_program: Interface<'info, TokenInterface>,
     associated_token_program: Program<'info, AssociatedToken>,
     system_program: Program<'info, System>,
@@ -64,9 +90,17 @@ pub struct Delegate<'info> {
 
 impl<'info> Delegate<'info> {
     // this delegate method is specific to solayer endo avs program for now
-    pub fn delegate(&mut self, amount: u64) -> Result<()> {
+    pub fn delegate(&mut self, amount: u64, avs_points_bump: u8) -> Result<()> {
+        self.accrue_points(avs_points_bump)?;
+
+        // referral rewards in the vault belong to the referrers
         self.pool_input_token_vault.reload()?;
-        if self.pool_input_token_vault.amount < amount {
+        if self
+            .pool_input_token_vault
+            .amount
+            .saturating_sub(self.pool.unclaimed_referral_rewards)
+            < amount
+        {
             return Err(LRTPoolError::InsufficientSSOLFundsForDelegate.into());
         }
 
@@ -106,6 +140,10 @@ impl<'info> Delegate<'info> {
         let output_token_mint = self.pool.output_token_mint.key();
         let signer_seeds: [&[&[u8]]; 1] = [&[b"lrt_pool", output_token_mint.as_ref(), &bump][..]];
 
+        // the points boost follows the avs tokens the pool actually received
+        self.pool_avs_token_vault.reload()?;
+        let avs_balance_before = self.pool_avs_token_vault.amount;
+
         invoke_signed(
             &delegate_inst,
             &[
@@ -121,11 +159,32 @@ impl<'info> Delegate<'info> {
                 self.system_program.to_account_info(),
             ],
             &signer_seeds,
-        )
-        .map_err(Into::into)
+        )?;
+
+        self.pool_avs_token_vault.reload()?;
+        let delegated_amount = self
+            .pool_avs_token_vault
+            .amount
+            .checked_sub(avs_balance_before)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        let (boost_before, boost_after) = self.avs_points.delegate(delegated_amount)?;
+        self.pool
+            .update_avs_points_boost(boost_before, boost_after)?;
+        self.pool
+            .refresh_exchange_rate(self.output_token_mint.supply);
+
+        emit!(Delegated {
+            pool: self.pool.key(),
+            avs: self.avs.key(),
+            amount,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
     }
 
-    pub fn undelegate(&mut self, amount: u64) -> Result<()> {
+    pub fn undelegate(&mut self, amount: u64, avs_points_bump: u8) -> Result<()> {
+        self.accrue_points(avs_points_bump)?;
+
         self.pool_avs_token_vault.reload()?;
         if self.pool_avs_token_vault.amount < amount {
             return Err(LRTPoolError::InsufficientAvsTokenForUndelegate.into());
@@ -167,6 +226,8 @@ impl<'info> Delegate<'info> {
         let output_token_mint = self.pool.output_token_mint.key();
         let signer_seeds: [&[&[u8]]; 1] = [&[b"lrt_pool", output_token_mint.as_ref(), &bump][..]];
 
+        let avs_balance_before = self.pool_avs_token_vault.amount;
+
         invoke_signed(
             &delegate_inst,
             &[
@@ -182,7 +243,36 @@ impl<'info> Delegate<'info> {
                 self.system_program.to_account_info(),
             ],
             &signer_seeds,
-        )
-        .map_err(Into::into)
+        )?;
+
+        // the points boost follows the avs tokens the pool actually gave back
+        self.pool_avs_token_vault.reload()?;
+        let undelegated_amount = avs_balance_before
+            .checked_sub(self.pool_avs_token_vault.amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        let (boost_before, boost_after) = self.avs_points.undelegate(undelegated_amount);
+        self.pool
+            .update_avs_points_boost(boost_before, boost_after)?;
+        self.pool
+            .refresh_exchange_rate(self.output_token_mint.supply);
+
+        emit!(Undelegated {
+            pool: self.pool.key(),
+            avs: self.avs.key(),
+            amount,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+
+    // points accrue at the current multiplier before the delegation changes it
+    fn accrue_points(&mut self, avs_points_bump: u8) -> Result<()> {
+        self.pool
+            .accrue_points(self.output_token_mint.supply, Clock::get()?.unix_timestamp)?;
+        let pool_key = self.pool.key();
+        let avs = self.avs.key();
+        self.avs_points
+            .init_if_needed(avs_points_bump, pool_key, avs);
+        Ok(())
     }
 }
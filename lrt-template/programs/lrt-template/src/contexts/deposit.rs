@@ -1,6 +1,5 @@
-use crate::state::*;
+use crate::{errors::LRTPoolError, events::Deposited, state::*};
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{
     mint_to, transfer_checked, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
 };
@@ -9,27 +8,32 @@ use anchor_spl::token_interface::{
 pub struct Deposit<'info> {
     #[account(mut)]
     signer: Signer<'info>,
+    /// CHECK: any owner of the minted output token, e.g. a vault program's PDA
+    recipient: UncheckedAccount<'info>,
 
     #[account(
-        mint::token_program = token_program,
+        mint::token_program = input_token_program,
         address = pool.input_token_mint
     )]
     input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    // user-side token accounts can be any token account of the right mint, not only ATAs
     #[account(
-        associated_token::authority = signer,
-        associated_token::mint = input_token_mint,
-        associated_token::token_program = token_program
+        mut,
+        token::authority = signer,
+        token::mint = input_token_mint,
+        token::token_program = input_token_program
     )]
     signer_input_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::authority = pool,
-        associated_token::mint = input_token_mint
+        associated_token::mint = input_token_mint,
+        associated_token::token_program = input_token_program
     )]
     pool_input_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
-        mint::token_program = token_program,
+        mint::token_program = output_token_program,
         mint::authority = pool,
         mint::freeze_authority = pool,
         mint::decimals = input_token_mint.decimals,
@@ -37,29 +41,93 @@ pub struct Deposit<'info> {
     )]
     output_token_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
-        init_if_needed,
-        payer = signer,
-        associated_token::authority = signer,
-        associated_token::mint = output_token_mint
+        mut,
+        token::authority = recipient,
+        token::mint = output_token_mint,
+        token::token_program = output_token_program
     )]
-    signer_output_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    recipient_output_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         has_one = input_token_mint,
         has_one = output_token_mint,
         seeds = [b"lrt_pool", output_token_mint.key().as_ref()],
-        bump = pool.bump
+        bump = pool.bump,
+        // the holding period lock only follows output tokens through the transfer hook
+        constraint = pool.transfer_hook_enabled @ LRTPoolError::TransferHookRequired
     )]
     pool: Box<Account<'info, LRTPool>>,
-    associated_token_program: Program<'info, AssociatedToken>,
-    token_program: Interface<'info, TokenInterface>,
+    // required for both the signer and the recipient while the pool's allowlist is enabled
+    #[account(
+        seeds = [b"allowlist", pool.key().as_ref(), signer.key().as_ref()],
+        bump = signer_allowlist_entry.bump
+    )]
+    signer_allowlist_entry: Option<Box<Account<'info, AllowlistEntry>>>,
+    #[account(
+        seeds = [b"allowlist", pool.key().as_ref(), recipient.key().as_ref()],
+        bump = recipient_allowlist_entry.bump
+    )]
+    recipient_allowlist_entry: Option<Box<Account<'info, AllowlistEntry>>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [b"user_position", pool.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    user_position: Box<Account<'info, UserPosition>>,
+    // credited with part of the deposit fee, registered beforehand with register_referrer
+    #[account(
+        mut,
+        has_one = pool,
+        seeds = [b"referrer_rewards", pool.key().as_ref(), referrer_rewards.referrer.as_ref()],
+        bump = referrer_rewards.bump
+    )]
+    referrer_rewards: Option<Box<Account<'info, ReferrerRewards>>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + HolderCheckpoint::INIT_SPACE,
+        seeds = [
+            b"holder_checkpoint",
+            pool.key().as_ref(),
+            recipient_output_token_vault.key().as_ref()
+        ],
+        bump
+    )]
+    recipient_checkpoint: Box<Account<'info, HolderCheckpoint>>,
+    input_token_program: Interface<'info, TokenInterface>,
+    output_token_program: Interface<'info, TokenInterface>,
     system_program: Program<'info, System>,
 }
 
 impl<'info> Deposit<'info> {
-    pub fn stake(&mut self, amount: u64) -> Result<()> {
+    pub fn check_allowlist(&self) -> Result<()> {
+        if self.pool.allowlist_enabled
+            && (self.signer_allowlist_entry.is_none() || self.recipient_allowlist_entry.is_none())
+        {
+            return Err(LRTPoolError::NotAllowlisted.into());
+        }
+        Ok(())
+    }
+
+    // vested rewards are recognized before converting at the exchange rate, and points
+    // accrue before the supply and the total assets change
+    pub fn accrue_rewards(&mut self) -> Result<()> {
+        let supply = self.output_token_mint.supply;
+        let clock = Clock::get()?;
+        self.pool.accrue_points(supply, clock.unix_timestamp)?;
+        self.pool.accrue_rewards(supply, &clock)?;
+        Ok(())
+    }
+
+    // returns the amount the pool actually received, which is less than amount
+    // when the input token mint charges a transfer fee
+    pub fn stake(&mut self, amount: u64) -> Result<u64> {
+        let balance_before = self.pool_input_token_vault.amount;
+
         let ctx = CpiContext::new(
-            self.token_program.to_account_info(),
+            self.input_token_program.to_account_info(),
             TransferChecked {
                 from: self.signer_input_token_vault.to_account_info(),
                 to: self.pool_input_token_vault.to_account_info(),
@@ -68,7 +136,130 @@ impl<'info> Deposit<'info> {
             },
         );
 
-        transfer_checked(ctx, amount, self.input_token_mint.decimals)
+        transfer_checked(ctx, amount, self.input_token_mint.decimals)?;
+
+        self.pool_input_token_vault.reload()?;
+        let received_amount = self
+            .pool_input_token_vault
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(LRTPoolError::MathOverflow)?;
+
+        self.check_deposit_caps(received_amount)?;
+        Ok(received_amount)
+    }
+
+    fn check_deposit_caps(&self, amount: u64) -> Result<()> {
+        let caps = self.pool.deposit_caps;
+
+        if let Some(deposit_cap) = caps.current_deposit_cap(Clock::get()?.unix_timestamp) {
+            require!(
+                self.pool.total_assets as u128 + amount as u128 <= deposit_cap as u128,
+                LRTPoolError::DepositCapExceeded
+            );
+        }
+
+        if caps.user_deposit_cap != 0 {
+            require!(
+                self.user_position.deposited_amount as u128 + amount as u128
+                    <= caps.user_deposit_cap as u128,
+                LRTPoolError::UserDepositCapExceeded
+            );
+        }
+        Ok(())
+    }
+
+    pub fn update_user_position(
+        &mut self,
+        input_amount: u64,
+        minted_amount: u64,
+        user_position_bump: u8,
+    ) -> Result<()> {
+        let pool = self.pool.key();
+        let user = self.recipient.key();
+        self.user_position
+            .init_if_needed(user_position_bump, pool, user);
+        self.user_position
+            .record_deposit(input_amount, minted_amount, Clock::get()?.slot)
+    }
+
+    // returns the fee kept by the pool and the referrer's share of it, which is credited
+    // to the referrer
+    pub fn take_deposit_fee(&mut self, amount: u64) -> Result<(u64, u64)> {
+        self.user_position.check_referrer(
+            self.referrer_rewards
+                .as_ref()
+                .map(|referrer_rewards| referrer_rewards.referrer),
+        )?;
+        let fee_amount = self.pool.deposit_fee(amount);
+
+        let mut referral_amount = 0;
+        if let Some(referrer_rewards) = self.referrer_rewards.as_mut() {
+            require!(
+                referrer_rewards.referrer != self.signer.key()
+                    && referrer_rewards.referrer != self.recipient.key(),
+                LRTPoolError::SelfReferral
+            );
+
+            referral_amount = self.pool.referral_fee(fee_amount);
+            referrer_rewards.unclaimed_amount = referrer_rewards
+                .unclaimed_amount
+                .checked_add(referral_amount)
+                .ok_or(LRTPoolError::MathOverflow)?;
+            referrer_rewards.total_earned = referrer_rewards
+                .total_earned
+                .checked_add(referral_amount)
+                .ok_or(LRTPoolError::MathOverflow)?;
+            self.pool.unclaimed_referral_rewards = self
+                .pool
+                .unclaimed_referral_rewards
+                .checked_add(referral_amount)
+                .ok_or(LRTPoolError::MathOverflow)?;
+
+            if self.user_position.referrer.is_none() {
+                self.user_position.referrer = Some(referrer_rewards.referrer);
+            }
+        }
+        Ok((fee_amount, referral_amount))
+    }
+
+    // the deposit fee stays in the pool and accrues to all output token holders
+    pub fn add_total_assets(&mut self, amount: u64) -> Result<()> {
+        self.pool.total_assets = self
+            .pool
+            .total_assets
+            .checked_add(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        self.output_token_mint.reload()?;
+        self.pool
+            .refresh_exchange_rate(self.output_token_mint.supply);
+        Ok(())
+    }
+
+    // settles the recipient's rewards and points before minting changes its balance
+    pub fn accrue_holder_rewards(&mut self, checkpoint_bump: u8) -> Result<()> {
+        let pool_key = self.pool.key();
+        let token_account = self.recipient_output_token_vault.key();
+        let now = Clock::get()?.unix_timestamp;
+        self.recipient_checkpoint.init_if_needed(
+            checkpoint_bump,
+            pool_key,
+            token_account,
+            &mut self.pool,
+            self.recipient_output_token_vault.amount,
+            now,
+        )?;
+        self.recipient_checkpoint
+            .accrue(&self.pool, self.recipient_output_token_vault.amount, now)
+    }
+
+    // minted tokens are locked for the holding period, deposits for someone else are locked
+    // apart from the recipient's own deposits
+    pub fn lock_output_token(&mut self, amount: u64) -> Result<()> {
+        let own = self.signer.key() == self.recipient.key();
+        let slot = Clock::get()?.slot;
+        self.recipient_checkpoint
+            .add_lock(own, amount, slot, self.pool.min_holding_slots, slot)
     }
 
     pub fn mint_output_token(&mut self, amount: u64) -> Result<()> {
@@ -81,20 +272,44 @@ impl<'info> Deposit<'info> {
         ][..]];
 
         let ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
+            self.output_token_program.to_account_info(),
             MintTo {
                 mint: self.output_token_mint.to_account_info(),
-                to: self.signer_output_token_vault.to_account_info(),
+                to: self.recipient_output_token_vault.to_account_info(),
                 authority: self.pool.to_account_info(),
             },
             &signer_seeds[..],
         );
 
-        mint_to(ctx, amount)
+        mint_to(ctx, amount)?;
+        // the recipient always has a checkpoint
+        self.pool.track_supply(amount)
+    }
+
+    pub fn emit_deposited(&self, input_amount: u64, fee_amount: u64, minted_amount: u64) {
+        emit!(Deposited {
+            pool: self.pool.key(),
+            user: self.signer.key(),
+            recipient: self.recipient.key(),
+            referrer: self
+                .referrer_rewards
+                .as_ref()
+                .map(|referrer_rewards| referrer_rewards.referrer),
+            input_amount,
+            fee_amount,
+            minted_amount,
+            exchange_rate: self.pool.exchange_rate(),
+        });
     }
 
-    // fill this function according to your business logic
-    pub fn calculate_output_token_amount(&self, amount: u64) -> u64 {
-        amount
+    // converts at the pool's exchange rate, adjust according to your business logic
+    pub fn calculate_output_token_amount(&self, amount: u64) -> Result<u64> {
+        let supply = self.output_token_mint.supply;
+        let total_assets = self.pool.total_assets;
+        if supply == 0 || total_assets == 0 {
+            return Ok(amount);
+        }
+        u64::try_from(amount as u128 * supply as u128 / total_assets as u128)
+            .map_err(|_| LRTPoolError::MathOverflow.into())
     }
 }
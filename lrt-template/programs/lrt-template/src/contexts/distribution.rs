@@ -0,0 +1,329 @@
+use crate::{
+    errors::LRTPoolError,
+    events::{DistributionClaimed, DistributionClosed, DistributionPublished},
+    state::*,
+    utils::verify_merkle_proof,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+use solana_program::hash::hashv;
+
+#[derive(Accounts)]
+#[instruction(
+    epoch: u64,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    num_claims: u32,
+    claim_deadline: i64
+)]
+pub struct PublishDistribution<'info> {
+    #[account(mut)]
+    admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump,
+        constraint = pool.admin == admin.key()
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    #[account(mint::token_program = reward_token_program)]
+    reward_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = reward_token_mint,
+        token::authority = admin,
+        token::token_program = reward_token_program
+    )]
+    admin_reward_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = admin,
+        space = Distribution::space(num_claims),
+        seeds = [
+            b"distribution",
+            pool.key().as_ref(),
+            reward_token_mint.key().as_ref(),
+            &epoch.to_le_bytes()
+        ],
+        bump
+    )]
+    distribution: Box<Account<'info, Distribution>>,
+    // one vault per distribution, so unclaimed allocations never mix with harvested rewards
+    #[account(
+        init,
+        payer = admin,
+        token::mint = reward_token_mint,
+        token::authority = pool,
+        token::token_program = reward_token_program,
+        seeds = [b"distribution_vault", distribution.key().as_ref()],
+        bump
+    )]
+    distribution_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    reward_token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> PublishDistribution<'info> {
+    pub fn publish_distribution(
+        &mut self,
+        epoch: u64,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        num_claims: u32,
+        claim_deadline: i64,
+        bumps: PublishDistributionBumps,
+    ) -> Result<()> {
+        require!(
+            total_amount > 0
+                && num_claims > 0
+                && num_claims <= MAX_DISTRIBUTION_CLAIMS
+                && claim_deadline > Clock::get()?.unix_timestamp,
+            LRTPoolError::InvalidDistribution
+        );
+
+        // the distribution is fully funded when it is published
+        let ctx = CpiContext::new(
+            self.reward_token_program.to_account_info(),
+            TransferChecked {
+                from: self.admin_reward_token_account.to_account_info(),
+                to: self.distribution_vault.to_account_info(),
+                mint: self.reward_token_mint.to_account_info(),
+                authority: self.admin.to_account_info(),
+            },
+        );
+        transfer_checked(ctx, total_amount, self.reward_token_mint.decimals)?;
+
+        self.distribution_vault.reload()?;
+        require!(
+            self.distribution_vault.amount == total_amount,
+            LRTPoolError::InvalidDistribution
+        );
+
+        self.distribution.set_inner(Distribution {
+            bump: bumps.distribution,
+            pool: self.pool.key(),
+            reward_token_mint: self.reward_token_mint.key(),
+            epoch,
+            merkle_root,
+            total_amount,
+            claimed_amount: 0,
+            num_claims,
+            claim_deadline,
+            claimed_bitmap: vec![0; Distribution::bitmap_len(num_claims)],
+        });
+
+        emit!(DistributionPublished {
+            pool: self.pool.key(),
+            distribution: self.distribution.key(),
+            reward_token_mint: self.reward_token_mint.key(),
+            epoch,
+            merkle_root,
+            total_amount,
+            num_claims,
+            claim_deadline,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimDistribution<'info> {
+    user: Signer<'info>,
+
+    #[account(
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    #[account(
+        mut,
+        has_one = pool,
+        has_one = reward_token_mint,
+        seeds = [
+            b"distribution",
+            pool.key().as_ref(),
+            reward_token_mint.key().as_ref(),
+            &distribution.epoch.to_le_bytes()
+        ],
+        bump = distribution.bump
+    )]
+    distribution: Box<Account<'info, Distribution>>,
+    #[account(mint::token_program = reward_token_program)]
+    reward_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"distribution_vault", distribution.key().as_ref()],
+        bump
+    )]
+    distribution_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = reward_token_mint,
+        token::token_program = reward_token_program
+    )]
+    user_reward_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    reward_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimDistribution<'info> {
+    // the merkle tree leaves are hash(index, user pubkey, reward token mint, amount)
+    pub fn claim_distribution(
+        &mut self,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp < self.distribution.claim_deadline,
+            LRTPoolError::DistributionExpired
+        );
+        require!(
+            index < self.distribution.num_claims,
+            LRTPoolError::InvalidMerkleProof
+        );
+        require!(
+            !self.distribution.is_claimed(index),
+            LRTPoolError::DistributionAlreadyClaimed
+        );
+
+        let leaf = hashv(&[
+            &index.to_le_bytes(),
+            self.user.key().as_ref(),
+            self.reward_token_mint.key().as_ref(),
+            &amount.to_le_bytes(),
+        ])
+        .to_bytes();
+        require!(
+            verify_merkle_proof(&proof, self.distribution.merkle_root, leaf),
+            LRTPoolError::InvalidMerkleProof
+        );
+
+        self.distribution.set_claimed(index);
+        self.distribution.claimed_amount = self
+            .distribution
+            .claimed_amount
+            .checked_add(amount)
+            .filter(|claimed| *claimed <= self.distribution.total_amount)
+            .ok_or(LRTPoolError::InvalidDistribution)?;
+
+        let bump = [self.pool.bump];
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"lrt_pool", self.pool.output_token_mint.as_ref(), &bump][..]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.reward_token_program.to_account_info(),
+            TransferChecked {
+                from: self.distribution_vault.to_account_info(),
+                to: self.user_reward_token_account.to_account_info(),
+                mint: self.reward_token_mint.to_account_info(),
+                authority: self.pool.to_account_info(),
+            },
+            &signer_seeds,
+        );
+        transfer_checked(ctx, amount, self.reward_token_mint.decimals)?;
+
+        emit!(DistributionClaimed {
+            pool: self.pool.key(),
+            distribution: self.distribution.key(),
+            user: self.user.key(),
+            index,
+            amount,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CloseDistribution<'info> {
+    #[account(mut)]
+    admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump,
+        constraint = pool.admin == admin.key()
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    #[account(
+        mut,
+        close = admin,
+        has_one = pool,
+        has_one = reward_token_mint,
+        seeds = [
+            b"distribution",
+            pool.key().as_ref(),
+            reward_token_mint.key().as_ref(),
+            &distribution.epoch.to_le_bytes()
+        ],
+        bump = distribution.bump
+    )]
+    distribution: Box<Account<'info, Distribution>>,
+    #[account(mint::token_program = reward_token_program)]
+    reward_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"distribution_vault", distribution.key().as_ref()],
+        bump
+    )]
+    distribution_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = reward_token_mint,
+        token::token_program = reward_token_program
+    )]
+    admin_reward_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    reward_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CloseDistribution<'info> {
+    // returns the unclaimed allocations and the rent of the vault once claiming has ended
+    pub fn close_distribution(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.distribution.claim_deadline,
+            LRTPoolError::DistributionNotExpired
+        );
+
+        let bump = [self.pool.bump];
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"lrt_pool", self.pool.output_token_mint.as_ref(), &bump][..]];
+
+        let unclaimed_amount = self.distribution_vault.amount;
+        if unclaimed_amount > 0 {
+            let ctx = CpiContext::new_with_signer(
+                self.reward_token_program.to_account_info(),
+                TransferChecked {
+                    from: self.distribution_vault.to_account_info(),
+                    to: self.admin_reward_token_account.to_account_info(),
+                    mint: self.reward_token_mint.to_account_info(),
+                    authority: self.pool.to_account_info(),
+                },
+                &signer_seeds,
+            );
+            transfer_checked(ctx, unclaimed_amount, self.reward_token_mint.decimals)?;
+        }
+
+        let ctx = CpiContext::new_with_signer(
+            self.reward_token_program.to_account_info(),
+            CloseAccount {
+                account: self.distribution_vault.to_account_info(),
+                destination: self.admin.to_account_info(),
+                authority: self.pool.to_account_info(),
+            },
+            &signer_seeds,
+        );
+        close_account(ctx)?;
+
+        emit!(DistributionClosed {
+            pool: self.pool.key(),
+            distribution: self.distribution.key(),
+            unclaimed_amount,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{errors::LRTPoolError, events::ChangeExecuted, state::*, utils::create_pda_account};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct ExecuteChange<'info> {
+    #[account(mut)]
+    admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = output_token_mint,
+        seeds = [b"lrt_pool", output_token_mint.key().as_ref()],
+        bump = pool.bump,
+        constraint = pool.admin == admin.key()
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        close = admin,
+        has_one = pool,
+        seeds = [b"pending_change", pool.key().as_ref(), pending_change.id.to_le_bytes().as_ref()],
+        bump = pending_change.bump
+    )]
+    pending_change: Account<'info, PendingChange>,
+
+    // required for avs points multiplier changes, created by the first delegation to the avs
+    #[account(mut, has_one = pool)]
+    avs_points: Option<Box<Account<'info, AvsPoints>>>,
+    /// CHECK: required for AddAvs changes, created in execute_change at
+    /// [b"avs_allowlist", pool, avs, avs_program]
+    #[account(mut)]
+    avs_allowlist: Option<UncheckedAccount<'info>>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteChange<'info> {
+    pub fn execute_change(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        if now < self.pending_change.eta {
+            return Err(LRTPoolError::TimelockNotElapsed.into());
+        }
+
+        match self.pending_change.change {
+            // both authorities still have to accept before they take over
+            ConfigChange::DelegateAuthority { new_authority } => {
+                self.pool.pending_delegate_authority = Some(new_authority);
+            }
+            ConfigChange::Admin { new_admin } => {
+                self.pool.pending_admin = Some(new_admin);
+            }
+            ConfigChange::Guardian { new_guardian } => {
+                self.pool.guardian = new_guardian;
+            }
+            ConfigChange::TimelockDelay { delay } => {
+                self.pool.timelock_delay = delay;
+            }
+            ConfigChange::DepositCaps { caps } => {
+                self.pool.deposit_caps = caps;
+            }
+            ConfigChange::Fees {
+                deposit_fee_bps,
+                referral_fee_share_bps,
+            } => {
+                self.pool.deposit_fee_bps = deposit_fee_bps;
+                self.pool.referral_fee_share_bps = referral_fee_share_bps;
+            }
+            ConfigChange::MinHoldingPeriod { slots } => {
+                self.pool.min_holding_slots = slots;
+            }
+            ConfigChange::RewardVestingPeriod { period } => {
+                self.pool.reward_vesting_period = period;
+            }
+            ConfigChange::RateLimits {
+                max_rate_increase_per_update_bps,
+                max_rate_increase_per_epoch_bps,
+            } => {
+                self.pool.max_rate_increase_per_update_bps = max_rate_increase_per_update_bps;
+                self.pool.max_rate_increase_per_epoch_bps = max_rate_increase_per_epoch_bps;
+            }
+            ConfigChange::Allowlist {
+                enabled,
+                merkle_root,
+            } => {
+                self.pool.allowlist_enabled = enabled;
+                self.pool.allowlist_merkle_root = merkle_root;
+            }
+            // points accrued so far keep the old rate
+            ConfigChange::PointsRate { points_rate } => {
+                self.pool
+                    .accrue_points(self.output_token_mint.supply, now)?;
+                self.pool.points_rate = points_rate;
+            }
+            // the multiplier applies to the share of the pool's assets delegated to the avs
+            ConfigChange::AvsPointsMultiplier {
+                avs,
+                multiplier_bps,
+            } => {
+                let avs_points = self
+                    .avs_points
+                    .as_mut()
+                    .filter(|avs_points| avs_points.avs == avs)
+                    .ok_or(LRTPoolError::InvalidAvsPoints)?;
+                self.pool
+                    .accrue_points(self.output_token_mint.supply, now)?;
+
+                let boost_before = avs_points.boost();
+                avs_points.multiplier_bps = multiplier_bps;
+                self.pool
+                    .update_avs_points_boost(boost_before, avs_points.boost())?;
+            }
+            ConfigChange::AddAvs { avs, avs_program } => {
+                self.add_avs(avs, avs_program)?;
+            }
+        }
+
+        emit!(ChangeExecuted {
+            pool: self.pool.key(),
+            pending_change: self.pending_change.key(),
+            change: self.pending_change.change.clone(),
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+
+    // the pool signs CPIs into avs programs, so only allowlisted ones can be used
+    fn add_avs(&self, avs: Pubkey, avs_program: Pubkey) -> Result<()> {
+        let avs_allowlist = self
+            .avs_allowlist
+            .as_ref()
+            .ok_or(LRTPoolError::InvalidAvsAllowlist)?;
+        let pool = self.pool.key();
+        let (address, bump) = Pubkey::find_program_address(
+            &[
+                b"avs_allowlist",
+                pool.as_ref(),
+                avs.as_ref(),
+                avs_program.as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            avs_allowlist.key(),
+            address,
+            LRTPoolError::InvalidAvsAllowlist
+        );
+
+        let bump_seed = [bump];
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"avs_allowlist",
+            pool.as_ref(),
+            avs.as_ref(),
+            avs_program.as_ref(),
+            &bump_seed,
+        ][..]];
+        let space = 8 + AvsAllowlist::INIT_SPACE;
+        create_pda_account(
+            &self.admin.to_account_info(),
+            &avs_allowlist.to_account_info(),
+            &self.system_program.to_account_info(),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &crate::ID,
+            &signer_seeds,
+        )?;
+
+        let mut data = avs_allowlist.try_borrow_mut_data()?;
+        AvsAllowlist {
+            bump,
+            pool,
+            avs,
+            avs_program,
+        }
+        .try_serialize(&mut &mut data[..])
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{errors::LRTPoolError, events::TransactionExecuted, state::*};
+use anchor_lang::prelude::*;
+use solana_program::{instruction::Instruction, program::invoke_signed};
+
+#[derive(Accounts)]
+pub struct ExecuteTransaction<'info> {
+    #[account(
+        seeds = [b"multisig", multisig.create_key.as_ref()],
+        bump = multisig.bump
+    )]
+    multisig: Account<'info, Multisig>,
+    #[account(
+        seeds = [b"multisig_signer", multisig.key().as_ref()],
+        bump = multisig.signer_bump
+    )]
+    multisig_signer: SystemAccount<'info>,
+    #[account(
+        mut,
+        has_one = multisig,
+        seeds = [
+            b"multisig_proposal",
+            multisig.key().as_ref(),
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    proposal: Account<'info, MultisigProposal>,
+}
+
+impl<'info> ExecuteTransaction<'info> {
+    // the proposal's accounts and program are passed as remaining accounts
+    pub fn execute_transaction(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        if self.proposal.owner_set_seqno != self.multisig.owner_set_seqno {
+            return Err(LRTPoolError::StaleMultisigProposal.into());
+        }
+        if self.proposal.executed {
+            return Err(LRTPoolError::MultisigProposalAlreadyExecuted.into());
+        }
+        if self.proposal.approval_count() < self.multisig.threshold as usize {
+            return Err(LRTPoolError::NotEnoughMultisigApprovals.into());
+        }
+
+        // persist the executed flag before the CPI so the proposal cannot be replayed from within it
+        self.proposal.executed = true;
+        self.proposal.exit(&crate::ID)?;
+
+        let multisig_signer = self.multisig_signer.key();
+        let accounts = self
+            .proposal
+            .accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.pubkey,
+                is_signer: account.is_signer || account.pubkey == multisig_signer,
+                is_writable: account.is_writable,
+            })
+            .collect();
+
+        let inst = Instruction {
+            program_id: self.proposal.program_id,
+            data: self.proposal.data.clone(),
+            accounts,
+        };
+
+        let bump = [self.multisig.signer_bump];
+        let multisig_key = self.multisig.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[b"multisig_signer", multisig_key.as_ref(), &bump][..]];
+
+        invoke_signed(&inst, remaining_accounts, &signer_seeds)?;
+
+        emit!(TransactionExecuted {
+            multisig: self.multisig.key(),
+            proposal: self.proposal.key(),
+        });
+        Ok(())
+    }
+}
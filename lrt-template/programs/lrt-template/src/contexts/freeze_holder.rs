@@ -0,0 +1,98 @@
+use crate::{
+    events::{HolderFrozen, HolderThawed},
+    state::LRTPool,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    freeze_account, thaw_account, FreezeAccount, Mint, ThawAccount, TokenAccount, TokenInterface,
+};
+
+#[derive(Accounts)]
+pub struct FreezeHolder<'info> {
+    #[account(
+        constraint = authority.key() == pool.admin || authority.key() == pool.guardian
+    )]
+    authority: Signer<'info>,
+    #[account(
+        mint::token_program = output_token_program,
+        mint::freeze_authority = pool,
+        address = pool.output_token_mint
+    )]
+    output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = output_token_mint,
+        token::token_program = output_token_program
+    )]
+    holder_output_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        has_one = output_token_mint,
+        seeds = [b"lrt_pool", output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    output_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> FreezeHolder<'info> {
+    // reason is an off-chain compliance code that is only recorded in the event
+    pub fn freeze(&mut self, reason: u16) -> Result<()> {
+        let bump = [self.pool.bump];
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"lrt_pool",
+            self.output_token_mint.to_account_info().key.as_ref(),
+            &bump,
+        ][..]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.output_token_program.to_account_info(),
+            FreezeAccount {
+                account: self.holder_output_token_vault.to_account_info(),
+                mint: self.output_token_mint.to_account_info(),
+                authority: self.pool.to_account_info(),
+            },
+            &signer_seeds,
+        );
+        freeze_account(ctx)?;
+
+        emit!(HolderFrozen {
+            pool: self.pool.key(),
+            token_account: self.holder_output_token_vault.key(),
+            owner: self.holder_output_token_vault.owner,
+            authority: self.authority.key(),
+            reason,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+
+    pub fn thaw(&mut self, reason: u16) -> Result<()> {
+        let bump = [self.pool.bump];
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"lrt_pool",
+            self.output_token_mint.to_account_info().key.as_ref(),
+            &bump,
+        ][..]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.output_token_program.to_account_info(),
+            ThawAccount {
+                account: self.holder_output_token_vault.to_account_info(),
+                mint: self.output_token_mint.to_account_info(),
+                authority: self.pool.to_account_info(),
+            },
+            &signer_seeds,
+        );
+        thaw_account(ctx)?;
+
+        emit!(HolderThawed {
+            pool: self.pool.key(),
+            token_account: self.holder_output_token_vault.key(),
+            owner: self.holder_output_token_vault.owner,
+            authority: self.authority.key(),
+            reason,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
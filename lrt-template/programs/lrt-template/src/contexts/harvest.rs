@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use solana_program::{instruction::Instruction, program::invoke_signed};
+
+use crate::{errors::LRTPoolError, events::RewardsHarvested, state::*, utils::sighash};
+
+#[derive(Accounts)]
+pub struct Harvest<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+
+    #[account(mut)]
+    avs: AccountInfo<'info>,
+    #[account(mint::token_program = reward_token_program)]
+    reward_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = reward_token_mint,
+        associated_token::authority = avs,
+        associated_token::token_program = reward_token_program
+    )]
+    avs_reward_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    // the pool input token vault when the rewards are paid in the input token
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = reward_token_mint,
+        associated_token::authority = pool,
+        associated_token::token_program = reward_token_program
+    )]
+    pool_reward_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + RewardVault::INIT_SPACE,
+        seeds = [b"reward_vault", pool.key().as_ref(), reward_token_mint.key().as_ref()],
+        bump
+    )]
+    reward_vault: Box<Account<'info, RewardVault>>,
+    #[account(address = pool.output_token_mint)]
+    output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump,
+        constraint = pool.delegate_authority == signer.key()
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    avs_program: AccountInfo<'info>,
+    // only an avs added through a timelocked AddAvs change can receive the pool's signature
+    #[account(
+        seeds = [
+            b"avs_allowlist",
+            pool.key().as_ref(),
+            avs.key().as_ref(),
+            avs_program.key().as_ref()
+        ],
+        bump = avs_allowlist.bump
+    )]
+    avs_allowlist: Box<Account<'info, AvsAllowlist>>,
+    reward_token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> Harvest<'info> {
+    // this harvest method expects a claim_rewards instruction on the avs program,
+    // adjust the accounts to the avs you delegate to
+    pub fn harvest(&mut self, bumps: HarvestBumps) -> Result<()> {
+        let balance_before = self.pool_reward_token_vault.amount;
+
+        let claim_data = sighash("global", "claim_rewards").to_vec();
+
+        let accounts = vec![
+            // staker
+            AccountMeta::new(self.pool.key(), true),
+            // avs
+            AccountMeta::new(self.avs.key(), false),
+            // rewardTokenMint
+            AccountMeta::new_readonly(self.reward_token_mint.key(), false),
+            // avsRewardTokenVault
+            AccountMeta::new(self.avs_reward_token_vault.key(), false),
+            // stakerRewardTokenAccount
+            AccountMeta::new(self.pool_reward_token_vault.key(), false),
+            // tokenProgram
+            AccountMeta::new_readonly(self.reward_token_program.key(), false),
+        ];
+
+        let claim_inst = Instruction {
+            program_id: self.avs_program.key(),
+            data: claim_data,
+            accounts,
+        };
+
+        let bump = [self.pool.bump];
+        let output_token_mint = self.pool.output_token_mint.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[b"lrt_pool", output_token_mint.as_ref(), &bump][..]];
+
+        invoke_signed(
+            &claim_inst,
+            &[
+                self.pool.to_account_info(),
+                self.avs.to_account_info(),
+                self.reward_token_mint.to_account_info(),
+                self.avs_reward_token_vault.to_account_info(),
+                self.pool_reward_token_vault.to_account_info(),
+                self.reward_token_program.to_account_info(),
+            ],
+            &signer_seeds,
+        )?;
+
+        self.pool_reward_token_vault.reload()?;
+        let amount = self
+            .pool_reward_token_vault
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(LRTPoolError::MathOverflow)?;
+
+        if self.reward_vault.pool == Pubkey::default() {
+            self.reward_vault.bump = bumps.reward_vault;
+            self.reward_vault.pool = self.pool.key();
+            self.reward_vault.reward_token_mint = self.reward_token_mint.key();
+        }
+        self.reward_vault.total_harvested = self
+            .reward_vault
+            .total_harvested
+            .checked_add(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+
+        // input token rewards back the output token and vest into the exchange rate,
+        // other reward tokens are distributed per output token
+        let supply = self.output_token_mint.supply;
+        let clock = Clock::get()?;
+        // points accrue at the current multiplier before the total assets change
+        self.pool.accrue_points(supply, clock.unix_timestamp)?;
+        if self.reward_token_mint.key() == self.pool.input_token_mint {
+            self.pool.vest_rewards(amount, supply, &clock)?;
+        } else {
+            // without the hook, tokens moved between checkpointed accounts would be paid twice
+            require!(
+                self.pool.transfer_hook_enabled,
+                LRTPoolError::TransferHookRequired
+            );
+            let index = self.pool.reward_token_index(self.reward_token_mint.key())?;
+            self.pool.distribute_rewards(index, amount)?;
+        }
+
+        emit!(RewardsHarvested {
+            pool: self.pool.key(),
+            avs: self.avs.key(),
+            reward_token_mint: self.reward_token_mint.key(),
+            amount,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
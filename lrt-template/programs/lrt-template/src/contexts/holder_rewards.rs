@@ -0,0 +1,130 @@
+use crate::{errors::LRTPoolError, events::HolderRewardsClaimed, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+// output token accounts that received tokens by transfer open a checkpoint to start earning
+#[derive(Accounts)]
+pub struct OpenHolderCheckpoint<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    #[account(token::mint = pool.output_token_mint)]
+    holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + HolderCheckpoint::INIT_SPACE,
+        seeds = [b"holder_checkpoint", pool.key().as_ref(), holder_token_account.key().as_ref()],
+        bump
+    )]
+    holder_checkpoint: Box<Account<'info, HolderCheckpoint>>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> OpenHolderCheckpoint<'info> {
+    pub fn open_holder_checkpoint(&mut self, bumps: OpenHolderCheckpointBumps) -> Result<()> {
+        let pool_key = self.pool.key();
+        let token_account = self.holder_token_account.key();
+        self.holder_checkpoint.init_if_needed(
+            bumps.holder_checkpoint,
+            pool_key,
+            token_account,
+            &mut self.pool,
+            self.holder_token_account.amount,
+            Clock::get()?.unix_timestamp,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimHolderRewards<'info> {
+    owner: Signer<'info>,
+
+    #[account(
+        token::mint = pool.output_token_mint,
+        token::authority = owner
+    )]
+    holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = pool,
+        seeds = [b"holder_checkpoint", pool.key().as_ref(), holder_token_account.key().as_ref()],
+        bump = holder_checkpoint.bump
+    )]
+    holder_checkpoint: Box<Account<'info, HolderCheckpoint>>,
+    #[account(
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump,
+        constraint = pool.transfer_hook_enabled @ LRTPoolError::TransferHookRequired
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    #[account(mint::token_program = reward_token_program)]
+    reward_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = reward_token_mint,
+        associated_token::authority = pool,
+        associated_token::token_program = reward_token_program
+    )]
+    pool_reward_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = reward_token_mint,
+        token::token_program = reward_token_program
+    )]
+    owner_reward_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    reward_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimHolderRewards<'info> {
+    pub fn claim_holder_rewards(&mut self) -> Result<()> {
+        let index = self
+            .pool
+            .reward_tokens
+            .iter()
+            .position(|reward_token| reward_token.mint == self.reward_token_mint.key())
+            .ok_or(LRTPoolError::UnknownRewardToken)?;
+
+        self.holder_checkpoint.accrue(
+            &self.pool,
+            self.holder_token_account.amount,
+            Clock::get()?.unix_timestamp,
+        )?;
+        let amount = self.holder_checkpoint.accrued_rewards[index];
+        self.holder_checkpoint.accrued_rewards[index] = 0;
+
+        let bump = [self.pool.bump];
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"lrt_pool", self.pool.output_token_mint.as_ref(), &bump][..]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.reward_token_program.to_account_info(),
+            TransferChecked {
+                from: self.pool_reward_token_vault.to_account_info(),
+                to: self.owner_reward_token_account.to_account_info(),
+                mint: self.reward_token_mint.to_account_info(),
+                authority: self.pool.to_account_info(),
+            },
+            &signer_seeds,
+        );
+        transfer_checked(ctx, amount, self.reward_token_mint.decimals)?;
+
+        emit!(HolderRewardsClaimed {
+            pool: self.pool.key(),
+            token_account: self.holder_token_account.key(),
+            owner: self.owner.key(),
+            reward_token_mint: self.reward_token_mint.key(),
+            amount,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
@@ -1,17 +1,50 @@
-use crate::{errors::LRTPoolError, state::*};
+use crate::{
+    contexts::{transfer_hook_extra_account_metas, TRANSFER_HOOK_EXTRA_ACCOUNTS},
+    errors::LRTPoolError,
+    events::PoolInitialized,
+    state::*,
+    utils::create_pda_account,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
+    token_2022::{
+        initialize_mint2,
+        spl_token_2022::{
+            extension::{
+                default_account_state::DefaultAccountState,
+                metadata_pointer,
+                mint_close_authority::MintCloseAuthority,
+                permanent_delegate::PermanentDelegate,
+                transfer_hook::{self, TransferHook},
+                BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+            },
+            state::{AccountState, Mint as MintState},
+        },
+        InitializeMint2, Token2022,
+    },
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
+use solana_program::program::{invoke, invoke_signed};
+use spl_pod::optional_keys::OptionalNonZeroPubkey;
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_token_metadata_interface::state::TokenMetadata;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_SYMBOL_LEN: usize = 10;
+pub const MAX_URI_LEN: usize = 200;
 
 #[derive(Accounts)]
+#[instruction(timelock_delay: i64, name: String, symbol: String)]
 pub struct Initialize<'info> {
     #[account(mut)]
     signer: Signer<'info>,
     delegate_authority: Signer<'info>,
+    admin: Signer<'info>,
+    guardian: Signer<'info>,
     #[account(
-        mint::token_program = token_program,
+        mint::token_program = input_token_program,
     )]
     input_token_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
@@ -19,17 +52,21 @@ pub struct Initialize<'info> {
         payer = signer,
         associated_token::authority = pool,
         associated_token::mint = input_token_mint,
-        associated_token::token_program = token_program
+        associated_token::token_program = input_token_program
     )]
     pool_input_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    // created in initialize as a Token-2022 mint carrying its own metadata
     #[account(
-        mint::decimals = input_token_mint.decimals,
-        mint::authority = pool,
-        mint::freeze_authority = pool,
-        mint::token_program = token_program,
-        constraint = output_token_mint.supply == 0 @ LRTPoolError::NonZeroRstMintSupply
+        mut,
+        seeds = [
+            b"output_token_mint",
+            input_token_mint.key().as_ref(),
+            admin.key().as_ref(),
+            symbol.as_bytes()
+        ],
+        bump
     )]
-    output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    output_token_mint: SystemAccount<'info>,
     #[account(
         init,
         payer=signer,
@@ -38,19 +75,252 @@ pub struct Initialize<'info> {
         bump
     )]
     pool: Box<Account<'info, LRTPool>>,
+    /// CHECK: extra accounts of the output token transfer hook, written in initialize
+    #[account(
+        init,
+        payer = signer,
+        space = ExtraAccountMetaList::size_of(TRANSFER_HOOK_EXTRA_ACCOUNTS).unwrap(),
+        seeds = [b"extra-account-metas", output_token_mint.key().as_ref()],
+        bump
+    )]
+    extra_account_meta_list: AccountInfo<'info>,
     associated_token_program: Program<'info, AssociatedToken>,
-    token_program: Interface<'info, TokenInterface>,
+    input_token_program: Interface<'info, TokenInterface>,
+    output_token_program: Program<'info, Token2022>,
     system_program: Program<'info, System>,
 }
 
 impl<'info> Initialize<'info> {
-    pub fn initialize(&mut self, bumps: InitializeBumps) -> Result<()> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        &mut self,
+        timelock_delay: i64,
+        name: String,
+        symbol: String,
+        uri: String,
+        accepted_input_mint_risks: u8,
+        deposit_caps: DepositCaps,
+        bumps: InitializeBumps,
+    ) -> Result<()> {
+        require!(timelock_delay >= 0, LRTPoolError::InvalidTimelockDelay);
+        require!(deposit_caps.is_valid(), LRTPoolError::InvalidDepositCaps);
+        self.check_input_token_mint(accepted_input_mint_risks)?;
+        require!(
+            name.len() <= MAX_NAME_LEN
+                && symbol.len() <= MAX_SYMBOL_LEN
+                && uri.len() <= MAX_URI_LEN,
+            LRTPoolError::InvalidTokenMetadata
+        );
+
+        self.create_output_token_mint(name, symbol, uri, &bumps)?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut self.extra_account_meta_list.try_borrow_mut_data()?,
+            &transfer_hook_extra_account_metas()?,
+        )?;
+
         self.pool.set_inner(LRTPool {
             bump: bumps.pool,
+            version: CURRENT_POOL_VERSION,
             input_token_mint: self.input_token_mint.key(),
             output_token_mint: self.output_token_mint.key(),
             delegate_authority: self.delegate_authority.key(),
+            pending_delegate_authority: None,
+            admin: self.admin.key(),
+            pending_admin: None,
+            guardian: self.guardian.key(),
+            timelock_delay,
+            change_nonce: 0,
+            accepted_input_mint_risks,
+            allowlist_enabled: false,
+            allowlist_merkle_root: [0; 32],
+            deposit_caps,
+            deposit_fee_bps: 0,
+            referral_fee_share_bps: 0,
+            unclaimed_referral_rewards: 0,
+            min_holding_slots: 0,
+            total_assets: 0,
+            last_exchange_rate: EXCHANGE_RATE_PRECISION,
+            max_rate_increase_per_update_bps: DEFAULT_MAX_RATE_INCREASE_PER_UPDATE_BPS,
+            max_rate_increase_per_epoch_bps: DEFAULT_MAX_RATE_INCREASE_PER_EPOCH_BPS,
+            rate_limit_epoch: 0,
+            epoch_start_rate: 0,
+            deferred_rewards: 0,
+            reward_vesting_period: DEFAULT_REWARD_VESTING_PERIOD,
+            unvested_rewards: 0,
+            vesting_end: 0,
+            last_vesting_update: 0,
+            reward_tokens: [RewardToken::default(); MAX_REWARD_TOKENS],
+            points_rate: 0,
+            points_per_token: 0,
+            total_points: 0,
+            last_points_update: 0,
+            avs_points_boost: 0,
+            tracked_supply: 0,
+            undistributed_rewards: [0; MAX_REWARD_TOKENS],
+            rate_limit_slot: 0,
+            slot_start_rate: 0,
+            transfer_hook_enabled: true,
+            reserved: [0; POOL_RESERVED_SPACE],
         });
+
+        emit!(PoolInitialized {
+            pool: self.pool.key(),
+            input_token_mint: self.pool.input_token_mint,
+            output_token_mint: self.pool.output_token_mint,
+            delegate_authority: self.pool.delegate_authority,
+            admin: self.pool.admin,
+            guardian: self.pool.guardian,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+
+    fn check_input_token_mint(&self, accepted_input_mint_risks: u8) -> Result<()> {
+        let mut risks = 0;
+        if self.input_token_mint.freeze_authority.is_some() {
+            risks |= input_mint_risk::FREEZE_AUTHORITY;
+        }
+
+        let mint_info = self.input_token_mint.to_account_info();
+        if *mint_info.owner == Token2022::id() {
+            let data = mint_info.try_borrow_data()?;
+            let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+
+            if let Ok(permanent_delegate) = mint.get_extension::<PermanentDelegate>() {
+                if Option::<Pubkey>::from(permanent_delegate.delegate).is_some() {
+                    risks |= input_mint_risk::PERMANENT_DELEGATE;
+                }
+            }
+            // the pool's input token transfers do not pass the accounts a hook program needs
+            if let Ok(transfer_hook) = mint.get_extension::<TransferHook>() {
+                require!(
+                    Option::<Pubkey>::from(transfer_hook.program_id).is_none(),
+                    LRTPoolError::UnsupportedInputTokenMint
+                );
+            }
+            // the pool input token vault would be created frozen and deposits could never succeed
+            if let Ok(default_state) = mint.get_extension::<DefaultAccountState>() {
+                require!(
+                    default_state.state != AccountState::Frozen as u8,
+                    LRTPoolError::UnsupportedInputTokenMint
+                );
+            }
+            if let Ok(close_authority) = mint.get_extension::<MintCloseAuthority>() {
+                if Option::<Pubkey>::from(close_authority.close_authority).is_some() {
+                    risks |= input_mint_risk::CLOSE_AUTHORITY;
+                }
+            }
+        }
+
+        require!(
+            risks & !accepted_input_mint_risks == 0,
+            LRTPoolError::UnsafeInputTokenMint
+        );
         Ok(())
     }
+
+    // the pool is mint authority, freeze authority and metadata update authority of the output token,
+    // transfers of it call this program's transfer hook to keep holder reward checkpoints current
+    fn create_output_token_mint(
+        &self,
+        name: String,
+        symbol: String,
+        uri: String,
+        bumps: &InitializeBumps,
+    ) -> Result<()> {
+        let mint = self.output_token_mint.key();
+        let pool = self.pool.key();
+
+        let metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(pool))?,
+            mint,
+            name,
+            symbol,
+            uri,
+            additional_metadata: vec![],
+        };
+        let mint_len = ExtensionType::try_calculate_account_len::<MintState>(&[
+            ExtensionType::MetadataPointer,
+            ExtensionType::TransferHook,
+        ])?;
+        // the token program reallocates the mint for the metadata but does not fund the extra rent
+        let lamports = Rent::get()?.minimum_balance(mint_len + metadata.tlv_size_of()?);
+
+        let input_token_mint = self.input_token_mint.key();
+        let admin = self.admin.key();
+        let mint_bump = [bumps.output_token_mint];
+        let mint_signer_seeds: [&[&[u8]]; 1] = [&[
+            b"output_token_mint",
+            input_token_mint.as_ref(),
+            admin.as_ref(),
+            metadata.symbol.as_bytes(),
+            &mint_bump,
+        ][..]];
+
+        create_pda_account(
+            &self.signer.to_account_info(),
+            &self.output_token_mint.to_account_info(),
+            &self.system_program.to_account_info(),
+            lamports,
+            mint_len as u64,
+            &self.output_token_program.key(),
+            &mint_signer_seeds,
+        )?;
+
+        invoke(
+            &metadata_pointer::instruction::initialize(
+                &self.output_token_program.key(),
+                &mint,
+                Some(pool),
+                Some(mint),
+            )?,
+            &[self.output_token_mint.to_account_info()],
+        )?;
+
+        invoke(
+            &transfer_hook::instruction::initialize(
+                &self.output_token_program.key(),
+                &mint,
+                None,
+                Some(crate::ID),
+            )?,
+            &[self.output_token_mint.to_account_info()],
+        )?;
+
+        initialize_mint2(
+            CpiContext::new(
+                self.output_token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: self.output_token_mint.to_account_info(),
+                },
+            ),
+            self.input_token_mint.decimals,
+            &pool,
+            Some(&pool),
+        )?;
+
+        let pool_bump = [bumps.pool];
+        let pool_signer_seeds: [&[&[u8]]; 1] = [&[b"lrt_pool", mint.as_ref(), &pool_bump][..]];
+
+        invoke_signed(
+            &spl_token_metadata_interface::instruction::initialize(
+                &self.output_token_program.key(),
+                &mint,
+                &pool,
+                &mint,
+                &pool,
+                metadata.name,
+                metadata.symbol,
+                metadata.uri,
+            ),
+            &[
+                self.output_token_mint.to_account_info(),
+                self.pool.to_account_info(),
+                self.output_token_mint.to_account_info(),
+                self.pool.to_account_info(),
+            ],
+            &pool_signer_seeds,
+        )
+        .map_err(Into::into)
+    }
 }
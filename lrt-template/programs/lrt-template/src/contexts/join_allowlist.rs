@@ -0,0 +1,55 @@
+use crate::{
+    errors::LRTPoolError, events::AllowlistEntryAdded, state::*, utils::verify_merkle_proof,
+};
+use anchor_lang::prelude::*;
+use solana_program::hash::hashv;
+
+#[derive(Accounts)]
+pub struct JoinAllowlist<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    #[account(
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Account<'info, LRTPool>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + AllowlistEntry::INIT_SPACE,
+        seeds = [b"allowlist", pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    allowlist_entry: Account<'info, AllowlistEntry>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> JoinAllowlist<'info> {
+    // the merkle tree leaves are hash(user pubkey)
+    pub fn join_allowlist(
+        &mut self,
+        proof: Vec<[u8; 32]>,
+        bumps: JoinAllowlistBumps,
+    ) -> Result<()> {
+        let leaf = hashv(&[self.user.key().as_ref()]).to_bytes();
+        require!(
+            verify_merkle_proof(&proof, self.pool.allowlist_merkle_root, leaf),
+            LRTPoolError::InvalidMerkleProof
+        );
+
+        self.allowlist_entry.set_inner(AllowlistEntry {
+            bump: bumps.allowlist_entry,
+            pool: self.pool.key(),
+            user: self.user.key(),
+        });
+
+        emit!(AllowlistEntryAdded {
+            pool: self.pool.key(),
+            user: self.user.key(),
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{errors::LRTPoolError, events::PoolMigrated, state::*};
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+use anchor_spl::{
+    token_2022::{
+        spl_token_2022::{
+            extension::{
+                transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions,
+            },
+            state::Mint as MintState,
+        },
+        Token2022,
+    },
+    token_interface::Mint,
+};
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    // checked manually in migrate_pool, older layouts do not deserialize as LRTPool
+    #[account(mut, owner = crate::ID)]
+    pool: UncheckedAccount<'info>,
+    // checked against the migrated pool in migrate_pool
+    output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> MigratePool<'info> {
+    pub fn migrate_pool(&mut self) -> Result<()> {
+        let pool_info = self.pool.to_account_info();
+        {
+            let data = pool_info.try_borrow_data()?;
+            if data.len() < 8 || data[..8] != LRTPool::DISCRIMINATOR {
+                return Err(LRTPoolError::UnknownPoolLayout.into());
+            }
+        }
+
+        let new_pool = match pool_info.data_len() {
+            len if len == 8 + LRTPoolV0::INIT_SPACE => self.migrate_from_v0()?,
+            // nothing to migrate until a later version carves new fields out of the reserved space
+            len if len == 8 + LRTPool::INIT_SPACE => {
+                return Err(LRTPoolError::PoolAlreadyMigrated.into())
+            }
+            _ => return Err(LRTPoolError::UnknownPoolLayout.into()),
+        };
+
+        let new_len = 8 + LRTPool::INIT_SPACE;
+        let rent_shortfall = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(pool_info.lamports());
+        if rent_shortfall > 0 {
+            let ctx = CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.signer.to_account_info(),
+                    to: pool_info.clone(),
+                },
+            );
+            transfer(ctx, rent_shortfall)?;
+        }
+        pool_info.realloc(new_len, true)?;
+
+        {
+            let mut data = pool_info.try_borrow_mut_data()?;
+            new_pool.try_serialize(&mut &mut data[..])?;
+        }
+
+        emit!(PoolMigrated {
+            pool: pool_info.key(),
+            version: new_pool.version,
+            exchange_rate: new_pool.exchange_rate(),
+        });
+        Ok(())
+    }
+
+    fn output_token_mint_has_transfer_hook(&self) -> Result<bool> {
+        let mint_info = self.output_token_mint.to_account_info();
+        if *mint_info.owner != Token2022::id() {
+            return Ok(false);
+        }
+        let data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+        Ok(mint
+            .get_extension::<TransferHook>()
+            .is_ok_and(|transfer_hook| {
+                Option::<Pubkey>::from(transfer_hook.program_id) == Some(crate::ID)
+            }))
+    }
+
+    fn migrate_from_v0(&self) -> Result<LRTPool> {
+        let pool = LRTPoolV0::deserialize(&mut &self.pool.try_borrow_data()?[8..])?;
+
+        let expected_pool = Pubkey::create_program_address(
+            &[b"lrt_pool", pool.output_token_mint.as_ref(), &[pool.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| LRTPoolError::UnknownPoolLayout)?;
+        require_keys_eq!(
+            expected_pool,
+            self.pool.key(),
+            LRTPoolError::UnknownPoolLayout
+        );
+        require_keys_eq!(
+            pool.output_token_mint,
+            self.output_token_mint.key(),
+            LRTPoolError::UnknownPoolLayout
+        );
+        // the delegate authority was the only privileged key before versioning
+        require_keys_eq!(
+            pool.delegate_authority,
+            self.signer.key(),
+            LRTPoolError::Unauthorized
+        );
+
+        Ok(LRTPool {
+            bump: pool.bump,
+            version: CURRENT_POOL_VERSION,
+            input_token_mint: pool.input_token_mint,
+            output_token_mint: pool.output_token_mint,
+            delegate_authority: pool.delegate_authority,
+            pending_delegate_authority: None,
+            admin: pool.delegate_authority,
+            pending_admin: None,
+            guardian: pool.delegate_authority,
+            timelock_delay: 0,
+            change_nonce: 0,
+            // the input token mint was accepted as is before these checks existed
+            accepted_input_mint_risks: 0,
+            allowlist_enabled: false,
+            allowlist_merkle_root: [0; 32],
+            deposit_caps: DepositCaps::default(),
+            deposit_fee_bps: 0,
+            referral_fee_share_bps: 0,
+            unclaimed_referral_rewards: 0,
+            min_holding_slots: 0,
+            // output tokens were minted 1:1 before assets were tracked
+            total_assets: self.output_token_mint.supply,
+            last_exchange_rate: EXCHANGE_RATE_PRECISION,
+            max_rate_increase_per_update_bps: DEFAULT_MAX_RATE_INCREASE_PER_UPDATE_BPS,
+            max_rate_increase_per_epoch_bps: DEFAULT_MAX_RATE_INCREASE_PER_EPOCH_BPS,
+            rate_limit_epoch: 0,
+            epoch_start_rate: 0,
+            deferred_rewards: 0,
+            reward_vesting_period: DEFAULT_REWARD_VESTING_PERIOD,
+            unvested_rewards: 0,
+            vesting_end: 0,
+            last_vesting_update: 0,
+            reward_tokens: [RewardToken::default(); MAX_REWARD_TOKENS],
+            points_rate: 0,
+            points_per_token: 0,
+            total_points: 0,
+            last_points_update: 0,
+            avs_points_boost: 0,
+            tracked_supply: 0,
+            undistributed_rewards: [0; MAX_REWARD_TOKENS],
+            rate_limit_slot: 0,
+            slot_start_rate: 0,
+            transfer_hook_enabled: self.output_token_mint_has_transfer_hook()?,
+            reserved: [0; POOL_RESERVED_SPACE],
+        })
+    }
+}
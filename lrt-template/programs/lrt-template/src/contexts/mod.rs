@@ -10,8 +10,80 @@ pub use withdraw::*;
 pub mod withdraw_stake;
 pub use withdraw_stake::*;
 
-pub mod transfer_delegate_authority;
-pub use transfer_delegate_authority::*;
+pub mod accept_delegate_authority;
+pub use accept_delegate_authority::*;
+
+pub mod cancel_delegate_authority;
+pub use cancel_delegate_authority::*;
+
+pub mod revoke_delegate_authority;
+pub use revoke_delegate_authority::*;
 
 pub mod delegate;
 pub use delegate::*;
+
+pub mod allowlist_user;
+pub use allowlist_user::*;
+
+pub mod join_allowlist;
+pub use join_allowlist::*;
+
+pub mod register_referrer;
+pub use register_referrer::*;
+
+pub mod claim_referral_rewards;
+pub use claim_referral_rewards::*;
+
+pub mod harvest;
+pub use harvest::*;
+
+pub mod update_total_assets;
+pub use update_total_assets::*;
+
+pub mod record_rate_snapshot;
+pub use record_rate_snapshot::*;
+
+pub mod holder_rewards;
+pub use holder_rewards::*;
+
+pub mod points;
+pub use points::*;
+
+pub mod distribution;
+pub use distribution::*;
+
+pub mod transfer_hook;
+pub use transfer_hook::*;
+
+pub mod freeze_holder;
+pub use freeze_holder::*;
+
+pub mod migrate_pool;
+pub use migrate_pool::*;
+
+pub mod accept_admin;
+pub use accept_admin::*;
+
+pub mod queue_change;
+pub use queue_change::*;
+
+pub mod execute_change;
+pub use execute_change::*;
+
+pub mod cancel_change;
+pub use cancel_change::*;
+
+pub mod create_multisig;
+pub use create_multisig::*;
+
+pub mod set_multisig_owners;
+pub use set_multisig_owners::*;
+
+pub mod propose_transaction;
+pub use propose_transaction::*;
+
+pub mod approve_transaction;
+pub use approve_transaction::*;
+
+pub mod execute_transaction;
+pub use execute_transaction::*;
@@ -0,0 +1,64 @@
+use crate::{events::PointsSynced, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+// permissionless, settles the points of an output token account
+#[derive(Accounts)]
+pub struct SyncPoints<'info> {
+    #[account(
+        mut,
+        has_one = output_token_mint,
+        seeds = [b"lrt_pool", output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(token::mint = output_token_mint)]
+    holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = pool,
+        seeds = [b"holder_checkpoint", pool.key().as_ref(), holder_token_account.key().as_ref()],
+        bump = holder_checkpoint.bump
+    )]
+    holder_checkpoint: Box<Account<'info, HolderCheckpoint>>,
+}
+
+impl<'info> SyncPoints<'info> {
+    pub fn sync_points(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        self.pool
+            .accrue_points(self.output_token_mint.supply, now)?;
+        self.holder_checkpoint
+            .accrue(&self.pool, self.holder_token_account.amount, now)?;
+
+        emit!(PointsSynced {
+            pool: self.pool.key(),
+            token_account: self.holder_token_account.key(),
+            owner: self.holder_token_account.owner,
+            points: self.holder_checkpoint.points,
+            total_points: self.pool.total_points,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ViewPoints<'info> {
+    #[account(
+        has_one = output_token_mint,
+        seeds = [b"lrt_pool", output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+impl<'info> ViewPoints<'info> {
+    // points of all holders up to now, tracked or not
+    pub fn total_points(&self) -> Result<u128> {
+        self.pool
+            .total_points_at(self.output_token_mint.supply, Clock::get()?.unix_timestamp)
+    }
+}
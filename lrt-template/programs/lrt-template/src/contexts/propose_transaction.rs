@@ -0,0 +1,69 @@
+use crate::{errors::LRTPoolError, events::TransactionProposed, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<ProposalAccount>, data: Vec<u8>)]
+pub struct ProposeTransaction<'info> {
+    #[account(mut)]
+    proposer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"multisig", multisig.create_key.as_ref()],
+        bump = multisig.bump
+    )]
+    multisig: Account<'info, Multisig>,
+    #[account(
+        init,
+        payer = proposer,
+        space = MultisigProposal::space(accounts.len(), data.len(), multisig.owners.len()),
+        seeds = [
+            b"multisig_proposal",
+            multisig.key().as_ref(),
+            multisig.proposal_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    proposal: Account<'info, MultisigProposal>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> ProposeTransaction<'info> {
+    pub fn propose_transaction(
+        &mut self,
+        program_id: Pubkey,
+        accounts: Vec<ProposalAccount>,
+        data: Vec<u8>,
+        bumps: ProposeTransactionBumps,
+    ) -> Result<()> {
+        let owner_index = self
+            .multisig
+            .owner_index(&self.proposer.key())
+            .ok_or(LRTPoolError::NotMultisigOwner)?;
+
+        // proposing counts as the proposer's approval
+        let mut approvals = vec![false; self.multisig.owners.len()];
+        approvals[owner_index] = true;
+
+        self.proposal.set_inner(MultisigProposal {
+            bump: bumps.proposal,
+            multisig: self.multisig.key(),
+            id: self.multisig.proposal_count,
+            proposer: self.proposer.key(),
+            program_id,
+            accounts,
+            data,
+            approvals,
+            owner_set_seqno: self.multisig.owner_set_seqno,
+            executed: false,
+        });
+        self.multisig.proposal_count += 1;
+
+        emit!(TransactionProposed {
+            multisig: self.multisig.key(),
+            proposal: self.proposal.key(),
+            proposer: self.proposer.key(),
+            program_id,
+        });
+        Ok(())
+    }
+}
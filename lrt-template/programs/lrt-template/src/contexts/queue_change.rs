@@ -0,0 +1,92 @@
+use crate::{errors::LRTPoolError, events::ChangeQueued, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct QueueChange<'info> {
+    #[account(mut)]
+    admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump,
+        constraint = pool.admin == admin.key()
+    )]
+    pool: Account<'info, LRTPool>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PendingChange::INIT_SPACE,
+        seeds = [b"pending_change", pool.key().as_ref(), pool.change_nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pending_change: Account<'info, PendingChange>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> QueueChange<'info> {
+    pub fn queue_change(&mut self, change: ConfigChange, bumps: QueueChangeBumps) -> Result<()> {
+        match change {
+            ConfigChange::TimelockDelay { delay } => {
+                require!(delay >= 0, LRTPoolError::InvalidTimelockDelay);
+            }
+            ConfigChange::RewardVestingPeriod { period } => {
+                require!(period >= 0, LRTPoolError::InvalidVestingPeriod);
+            }
+            ConfigChange::DepositCaps { caps } => {
+                require!(caps.is_valid(), LRTPoolError::InvalidDepositCaps);
+            }
+            ConfigChange::Fees {
+                deposit_fee_bps,
+                referral_fee_share_bps,
+            } => {
+                require!(
+                    deposit_fee_bps <= MAX_DEPOSIT_FEE_BPS
+                        && referral_fee_share_bps <= BPS_DENOMINATOR,
+                    LRTPoolError::InvalidFees
+                );
+            }
+            ConfigChange::RateLimits {
+                max_rate_increase_per_update_bps,
+                max_rate_increase_per_epoch_bps,
+            } => {
+                require!(
+                    max_rate_increase_per_update_bps <= MAX_RATE_INCREASE_BPS
+                        && max_rate_increase_per_epoch_bps <= MAX_RATE_INCREASE_BPS,
+                    LRTPoolError::InvalidRateLimits
+                );
+            }
+            ConfigChange::AvsPointsMultiplier { multiplier_bps, .. } => {
+                require!(
+                    multiplier_bps >= BPS_DENOMINATOR,
+                    LRTPoolError::InvalidPointsMultiplier
+                );
+            }
+            _ => {}
+        }
+
+        let eta = Clock::get()?
+            .unix_timestamp
+            .checked_add(self.pool.timelock_delay)
+            .ok_or(LRTPoolError::MathOverflow)?;
+
+        self.pending_change.set_inner(PendingChange {
+            bump: bumps.pending_change,
+            pool: self.pool.key(),
+            id: self.pool.change_nonce,
+            change,
+            eta,
+        });
+        self.pool.change_nonce += 1;
+
+        emit!(ChangeQueued {
+            pool: self.pool.key(),
+            pending_change: self.pending_change.key(),
+            change: self.pending_change.change.clone(),
+            eta,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
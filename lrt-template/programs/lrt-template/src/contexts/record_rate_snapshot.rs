@@ -0,0 +1,96 @@
+use crate::{errors::LRTPoolError, events::RateSnapshotRecorded, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+// permissionless crank
+#[derive(Accounts)]
+pub struct RecordRateSnapshot<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = output_token_mint,
+        seeds = [b"lrt_pool", output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RateHistory::INIT_SPACE,
+        seeds = [b"rate_history", pool.key().as_ref()],
+        bump
+    )]
+    rate_history: Box<Account<'info, RateHistory>>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> RecordRateSnapshot<'info> {
+    pub fn record_rate_snapshot(&mut self, bumps: RecordRateSnapshotBumps) -> Result<()> {
+        if self.rate_history.pool == Pubkey::default() {
+            self.rate_history.bump = bumps.rate_history;
+            self.rate_history.pool = self.pool.key();
+        }
+
+        let clock = Clock::get()?;
+        if let Some(latest) = self.rate_history.latest() {
+            require!(
+                clock.epoch > latest.epoch,
+                LRTPoolError::RateSnapshotTooEarly
+            );
+        }
+
+        // the snapshot includes the rewards vested so far
+        let supply = self.output_token_mint.supply;
+        self.pool.accrue_points(supply, clock.unix_timestamp)?;
+        self.pool.accrue_rewards(supply, &clock)?;
+
+        let snapshot = RateSnapshot {
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+            epoch: clock.epoch,
+            total_assets: self.pool.total_assets,
+            supply,
+        };
+        self.rate_history.push(snapshot);
+        self.pool.refresh_exchange_rate(snapshot.supply);
+
+        emit!(RateSnapshotRecorded {
+            pool: self.pool.key(),
+            slot: snapshot.slot,
+            epoch: snapshot.epoch,
+            total_assets: snapshot.total_assets,
+            supply: snapshot.supply,
+            exchange_rate: snapshot.exchange_rate(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ViewRateHistory<'info> {
+    #[account(
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    #[account(
+        has_one = pool,
+        seeds = [b"rate_history", pool.key().as_ref()],
+        bump = rate_history.bump
+    )]
+    rate_history: Box<Account<'info, RateHistory>>,
+}
+
+impl<'info> ViewRateHistory<'info> {
+    // window in seconds, e.g. 7 days for a weekly twap
+    pub fn twap_exchange_rate(&self, window: i64) -> Result<u64> {
+        require!(window > 0, LRTPoolError::InvalidTwapWindow);
+        let now = Clock::get()?.unix_timestamp;
+        self.rate_history
+            .twap(now.saturating_sub(window), now)
+            .ok_or(LRTPoolError::RateHistoryEmpty.into())
+    }
+}
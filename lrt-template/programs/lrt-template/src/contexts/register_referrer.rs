@@ -0,0 +1,38 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    referrer: Signer<'info>,
+
+    #[account(
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Account<'info, LRTPool>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferrerRewards::INIT_SPACE,
+        seeds = [b"referrer_rewards", pool.key().as_ref(), referrer.key().as_ref()],
+        bump
+    )]
+    referrer_rewards: Account<'info, ReferrerRewards>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterReferrer<'info> {
+    pub fn register_referrer(&mut self, bumps: RegisterReferrerBumps) -> Result<()> {
+        self.referrer_rewards.set_inner(ReferrerRewards {
+            bump: bumps.referrer_rewards,
+            pool: self.pool.key(),
+            referrer: self.referrer.key(),
+            unclaimed_amount: 0,
+            total_earned: 0,
+            total_claimed: 0,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{events::DelegateAuthorityRevoked, state::LRTPool};
+use anchor_lang::prelude::*;
+
+// takes effect immediately, only appointing a new delegate authority is timelocked
+#[derive(Accounts)]
+pub struct RevokeDelegateAuthority<'info> {
+    #[account(
+        constraint = authority.key() == pool.admin || authority.key() == pool.guardian
+    )]
+    authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lrt_pool", pool.output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Account<'info, LRTPool>,
+}
+
+impl<'info> RevokeDelegateAuthority<'info> {
+    // nobody can sign for the default pubkey, so delegating stops until a new key accepts
+    pub fn revoke_delegate_authority(&mut self) -> Result<()> {
+        let old_authority = self.pool.delegate_authority;
+        self.pool.delegate_authority = Pubkey::default();
+
+        emit!(DelegateAuthorityRevoked {
+            pool: self.pool.key(),
+            old_authority,
+            revoked_by: self.authority.key(),
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{contexts::validate_owners, events::MultisigOwnersSet, state::*};
+use anchor_lang::prelude::*;
+
+// can only be signed by the multisig signer, i.e. through an executed proposal
+#[derive(Accounts)]
+pub struct SetMultisigOwners<'info> {
+    #[account(
+        mut,
+        seeds = [b"multisig", multisig.create_key.as_ref()],
+        bump = multisig.bump
+    )]
+    multisig: Account<'info, Multisig>,
+    #[account(
+        seeds = [b"multisig_signer", multisig.key().as_ref()],
+        bump = multisig.signer_bump
+    )]
+    multisig_signer: Signer<'info>,
+}
+
+impl<'info> SetMultisigOwners<'info> {
+    pub fn set_owners(&mut self, owners: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        validate_owners(&owners, threshold)?;
+
+        self.multisig.owners = owners;
+        self.multisig.threshold = threshold;
+        self.multisig.owner_set_seqno += 1;
+
+        emit!(MultisigOwnersSet {
+            multisig: self.multisig.key(),
+            owners: self.multisig.owners.clone(),
+            threshold,
+            owner_set_seqno: self.multisig.owner_set_seqno,
+        });
+        Ok(())
+    }
+}
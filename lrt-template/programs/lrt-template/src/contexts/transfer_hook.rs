@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        extension::{
+            transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions,
+        },
+        state::Account as TokenAccountState,
+    },
+    token_interface::{Mint, TokenAccount},
+};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+
+use crate::{errors::LRTPoolError, state::*};
+
+pub const TRANSFER_HOOK_EXTRA_ACCOUNTS: usize = 3;
+
+// the pool and the checkpoints of both token accounts, resolved from the accounts of the
+// transfer: 0 source, 1 mint, 2 destination, 3 owner, 4 this list, 5 the pool. the pool is
+// writable to keep its tracked supply current
+pub fn transfer_hook_extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: b"lrt_pool".to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: b"holder_checkpoint".to_vec(),
+                },
+                Seed::AccountKey { index: 5 },
+                Seed::AccountKey { index: 0 },
+            ],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: b"holder_checkpoint".to_vec(),
+                },
+                Seed::AccountKey { index: 5 },
+                Seed::AccountKey { index: 2 },
+            ],
+            false,
+            true,
+        )?,
+    ])
+}
+
+// called by the token program on every output token transfer
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    source_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(token::mint = mint)]
+    destination_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: owner or delegate of the source token account, checked by the token program
+    owner: UncheckedAccount<'info>,
+    /// CHECK: the extra account meta list written by initialize
+    #[account(seeds = [b"extra-account-metas", mint.key().as_ref()], bump)]
+    extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"lrt_pool", mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    /// CHECK: holder checkpoint of the source, may not exist
+    #[account(mut)]
+    source_checkpoint: UncheckedAccount<'info>,
+    /// CHECK: holder checkpoint of the destination, may not exist
+    #[account(mut)]
+    destination_checkpoint: UncheckedAccount<'info>,
+}
+
+impl<'info> TransferHook<'info> {
+    pub fn transfer_hook(&mut self, amount: u64) -> Result<()> {
+        self.check_transferring()?;
+        let clock = Clock::get()?;
+
+        // the balances were already moved, accrue on the balances before the transfer
+        if self.source_token.key() == self.destination_token.key() {
+            let mut checkpoint =
+                load_checkpoint(&self.source_checkpoint, &self.pool, self.source_token.key())?;
+            if let Some(checkpoint) = checkpoint.as_mut() {
+                checkpoint.accrue(&self.pool, self.source_token.amount, clock.unix_timestamp)?;
+            }
+            return store_checkpoint(&self.source_checkpoint, checkpoint);
+        }
+        let source_balance = self
+            .source_token
+            .amount
+            .checked_add(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        let destination_balance = self
+            .destination_token
+            .amount
+            .checked_sub(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+
+        let mut source_checkpoint =
+            load_checkpoint(&self.source_checkpoint, &self.pool, self.source_token.key())?;
+        let mut destination_checkpoint = load_checkpoint(
+            &self.destination_checkpoint,
+            &self.pool,
+            self.destination_token.key(),
+        )?;
+
+        // tokens in their holding period stay locked in the destination
+        let min_holding_slots = self.pool.min_holding_slots;
+        let (locked_amount, lock_slot) = match source_checkpoint.as_mut() {
+            Some(checkpoint) => {
+                checkpoint.accrue(&self.pool, source_balance, clock.unix_timestamp)?;
+                checkpoint.release_transferred_lock(
+                    source_balance,
+                    amount,
+                    min_holding_slots,
+                    clock.slot,
+                )
+            }
+            None => (0, 0),
+        };
+        match destination_checkpoint.as_mut() {
+            Some(checkpoint) => {
+                checkpoint.accrue(&self.pool, destination_balance, clock.unix_timestamp)?;
+                if locked_amount != 0 {
+                    checkpoint.add_lock(
+                        false,
+                        locked_amount,
+                        lock_slot,
+                        min_holding_slots,
+                        clock.slot,
+                    )?;
+                }
+            }
+            None => require!(locked_amount == 0, LRTPoolError::HoldingPeriodNotElapsed),
+        }
+
+        // reward tokens are distributed over the output tokens held by tracked accounts
+        match (
+            source_checkpoint.is_some(),
+            destination_checkpoint.is_some(),
+        ) {
+            (true, false) => self.pool.untrack_supply(amount),
+            (false, true) => self.pool.track_supply(amount)?,
+            _ => {}
+        }
+
+        store_checkpoint(&self.source_checkpoint, source_checkpoint)?;
+        store_checkpoint(&self.destination_checkpoint, destination_checkpoint)
+    }
+
+    // only the token program sets the transferring flag, so the hook cannot be called directly
+    fn check_transferring(&self) -> Result<()> {
+        let source_info = self.source_token.to_account_info();
+        let data = source_info.try_borrow_data()?;
+        let account = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+        let extension = account.get_extension::<TransferHookAccount>()?;
+        require!(
+            bool::from(extension.transferring),
+            LRTPoolError::NotTransferring
+        );
+        Ok(())
+    }
+}
+
+// token accounts without a checkpoint do not earn rewards or points until one is opened
+fn load_checkpoint(
+    checkpoint_info: &AccountInfo,
+    pool: &Account<LRTPool>,
+    token_account: Pubkey,
+) -> Result<Option<HolderCheckpoint>> {
+    if *checkpoint_info.owner != crate::ID || checkpoint_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    let checkpoint =
+        HolderCheckpoint::try_deserialize(&mut &checkpoint_info.try_borrow_data()?[..])?;
+    require_keys_eq!(checkpoint.pool, pool.key(), LRTPoolError::Unauthorized);
+    require_keys_eq!(
+        checkpoint.token_account,
+        token_account,
+        LRTPoolError::Unauthorized
+    );
+    Ok(Some(checkpoint))
+}
+
+fn store_checkpoint(
+    checkpoint_info: &AccountInfo,
+    checkpoint: Option<HolderCheckpoint>,
+) -> Result<()> {
+    match checkpoint {
+        Some(checkpoint) => {
+            checkpoint.try_serialize(&mut &mut checkpoint_info.try_borrow_mut_data()?[..])
+        }
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{events::TotalAssetsUpdated, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+// permissionless crank, recognizes vested and deferred rewards as far as the rate limits allow
+#[derive(Accounts)]
+pub struct UpdateTotalAssets<'info> {
+    #[account(
+        mut,
+        has_one = output_token_mint,
+        seeds = [b"lrt_pool", output_token_mint.key().as_ref()],
+        bump = pool.bump
+    )]
+    pool: Box<Account<'info, LRTPool>>,
+    output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+impl<'info> UpdateTotalAssets<'info> {
+    pub fn update_total_assets(&mut self) -> Result<()> {
+        let supply = self.output_token_mint.supply;
+        let clock = Clock::get()?;
+        // the points multiplier depends on the total assets
+        self.pool.accrue_points(supply, clock.unix_timestamp)?;
+        let recognized_amount = self.pool.accrue_rewards(supply, &clock)?;
+
+        emit!(TotalAssetsUpdated {
+            pool: self.pool.key(),
+            recognized_amount,
+            deferred_amount: self.pool.deferred_rewards,
+            total_assets: self.pool.total_assets,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+        Ok(())
+    }
+}
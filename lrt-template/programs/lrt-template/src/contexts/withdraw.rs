@@ -1,41 +1,42 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    associated_token::AssociatedToken,
     token_2022::Burn,
     token_interface::{
         burn, transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
     },
 };
 
-use crate::{errors::LRTPoolError, state::LRTPool};
+use crate::{errors::LRTPoolError, events::Withdrawn, state::*};
 
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
     #[account(mut)]
     signer: Signer<'info>,
+    /// CHECK: any owner of a token account of the input token mint
+    recipient: UncheckedAccount<'info>,
     #[account(
-        mut,
-        mint::token_program = token_program,
+        mint::token_program = input_token_program,
         address = pool.input_token_mint
     )]
     input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    // user-side token accounts can be any token account of the right mint, not only ATAs
     #[account(
         mut,
-        associated_token::authority = signer,
-        associated_token::mint = input_token_mint,
-        associated_token::token_program = token_program
+        token::authority = recipient,
+        token::mint = input_token_mint,
+        token::token_program = input_token_program
     )]
-    signer_input_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    recipient_input_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::authority = pool,
         associated_token::mint = input_token_mint,
-        associated_token::token_program = token_program
+        associated_token::token_program = input_token_program
     )]
     pool_input_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
-        mint::token_program = token_program,
+        mint::token_program = output_token_program,
         mint::authority = pool,
         mint::freeze_authority = pool,
         mint::decimals = input_token_mint.decimals,
@@ -44,9 +45,9 @@ pub struct Withdraw<'info> {
     output_token_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         mut,
-        associated_token::authority = signer,
-        associated_token::mint = output_token_mint,
-        associated_token::token_program = token_program
+        token::authority = signer,
+        token::mint = output_token_mint,
+        token::token_program = output_token_program
     )]
     signer_output_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
@@ -57,27 +58,96 @@ pub struct Withdraw<'info> {
         bump = pool.bump
     )]
     pool: Box<Account<'info, LRTPool>>,
-    associated_token_program: Program<'info, AssociatedToken>,
-    token_program: Interface<'info, TokenInterface>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + HolderCheckpoint::INIT_SPACE,
+        seeds = [
+            b"holder_checkpoint",
+            pool.key().as_ref(),
+            signer_output_token_vault.key().as_ref()
+        ],
+        bump
+    )]
+    signer_checkpoint: Box<Account<'info, HolderCheckpoint>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [b"user_position", pool.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    user_position: Box<Account<'info, UserPosition>>,
+    input_token_program: Interface<'info, TokenInterface>,
+    output_token_program: Interface<'info, TokenInterface>,
     system_program: Program<'info, System>,
 }
 
 impl<'info> Withdraw<'info> {
+    // vested rewards are recognized before converting at the exchange rate, and points
+    // accrue before the supply and the total assets change
+    pub fn accrue_rewards(&mut self) -> Result<()> {
+        let supply = self.output_token_mint.supply;
+        let clock = Clock::get()?;
+        self.pool.accrue_points(supply, clock.unix_timestamp)?;
+        self.pool.accrue_rewards(supply, &clock)?;
+        Ok(())
+    }
+
+    // output tokens minted or received in their holding period cannot be burned yet
+    pub fn check_holding_period(&self, amount: u64) -> Result<()> {
+        self.signer_checkpoint.check_unlocked(
+            self.signer_output_token_vault.amount,
+            amount,
+            self.pool.min_holding_slots,
+            Clock::get()?.slot,
+        )
+    }
+
+    // settles the signer's rewards and points before burning changes its balance
+    pub fn accrue_holder_rewards(&mut self, checkpoint_bump: u8) -> Result<()> {
+        let pool_key = self.pool.key();
+        let token_account = self.signer_output_token_vault.key();
+        let now = Clock::get()?.unix_timestamp;
+        self.signer_checkpoint.init_if_needed(
+            checkpoint_bump,
+            pool_key,
+            token_account,
+            &mut self.pool,
+            self.signer_output_token_vault.amount,
+            now,
+        )?;
+        self.signer_checkpoint
+            .accrue(&self.pool, self.signer_output_token_vault.amount, now)
+    }
+
     pub fn burn_output_token(&mut self, amount: u64) -> Result<()> {
         let ctx = CpiContext::new(
-            self.token_program.to_account_info(),
+            self.output_token_program.to_account_info(),
             Burn {
                 mint: self.output_token_mint.to_account_info(),
                 from: self.signer_output_token_vault.to_account_info(),
                 authority: self.signer.to_account_info(),
             },
         );
-        burn(ctx, amount)
+        burn(ctx, amount)?;
+        self.pool.untrack_supply(amount);
+        Ok(())
     }
 
-    pub fn unstake(&mut self, amount: u64) -> Result<()> {
+    // returns the amount the user actually received, which is less than amount
+    // when the input token mint charges a transfer fee
+    pub fn unstake(&mut self, amount: u64) -> Result<u64> {
+        let balance_before = self.recipient_input_token_vault.amount;
+
+        // referral rewards in the vault belong to the referrers
         self.pool_input_token_vault.reload()?;
-        if self.pool_input_token_vault.amount < amount {
+        if self
+            .pool_input_token_vault
+            .amount
+            .saturating_sub(self.pool.unclaimed_referral_rewards)
+            < amount
+        {
             return Err(LRTPoolError::InsufficientStakedSOLFundsForWithdraw.into());
         }
 
@@ -89,21 +159,70 @@ impl<'info> Withdraw<'info> {
         ][..]];
 
         let ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
+            self.input_token_program.to_account_info(),
             TransferChecked {
                 from: self.pool_input_token_vault.to_account_info(),
-                to: self.signer_input_token_vault.to_account_info(),
+                to: self.recipient_input_token_vault.to_account_info(),
                 mint: self.input_token_mint.to_account_info(),
                 authority: self.pool.to_account_info(),
             },
             &signer_seeds,
         );
 
-        transfer_checked(ctx, amount, self.input_token_mint.decimals)
+        transfer_checked(ctx, amount, self.input_token_mint.decimals)?;
+
+        self.recipient_input_token_vault.reload()?;
+        self.recipient_input_token_vault
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(LRTPoolError::MathOverflow.into())
+    }
+
+    // positions of users who deposited before positions were tracked are created here
+    pub fn update_user_position(
+        &mut self,
+        input_amount: u64,
+        burned_amount: u64,
+        user_position_bump: u8,
+    ) -> Result<()> {
+        let pool = self.pool.key();
+        let user = self.signer.key();
+        self.user_position
+            .init_if_needed(user_position_bump, pool, user);
+        self.user_position
+            .record_withdrawal(input_amount, burned_amount)
+    }
+
+    pub fn emit_withdrawn(&self, burned_amount: u64, output_amount: u64) {
+        emit!(Withdrawn {
+            pool: self.pool.key(),
+            user: self.signer.key(),
+            recipient: self.recipient.key(),
+            burned_amount,
+            output_amount,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+    }
+
+    // converts at the pool's exchange rate, adjust according to your business logic
+    pub fn calculate_input_token_amount(&self, amount: u64) -> Result<u64> {
+        let supply = self.output_token_mint.supply;
+        if supply == 0 {
+            return Ok(amount);
+        }
+        u64::try_from(amount as u128 * self.pool.total_assets as u128 / supply as u128)
+            .map_err(|_| LRTPoolError::MathOverflow.into())
     }
 
-    // fill this function according to your business logic
-    pub fn calculate_input_token_amount(&self, amount: u64) -> u64 {
-        amount
+    pub fn sub_total_assets(&mut self, amount: u64) -> Result<()> {
+        self.pool.total_assets = self
+            .pool
+            .total_assets
+            .checked_sub(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        self.output_token_mint.reload()?;
+        self.pool
+            .refresh_exchange_rate(self.output_token_mint.supply);
+        Ok(())
     }
 }
@@ -1,4 +1,4 @@
-use crate::{errors::LRTPoolError, state::LRTPool, utils::sighash};
+use crate::{errors::LRTPoolError, events::DelegatedStakeWithdrawn, state::*, utils::sighash};
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
@@ -12,30 +12,32 @@ use solana_program::{instruction::Instruction, program::invoke_signed};
 pub struct WithdrawStake<'info> {
     #[account(mut)]
     signer: Signer<'info>,
+    /// CHECK: any owner of a token account of the input token mint
+    recipient: UncheckedAccount<'info>,
     #[account(
         mut,
-        mint::token_program = token_program,
+        mint::token_program = input_token_program,
         address = pool.input_token_mint
     )]
     input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    // user-side token accounts can be any token account of the right mint, not only ATAs
     #[account(
-        init_if_needed,
-        payer = signer,
-        associated_token::authority = signer,
-        associated_token::mint = input_token_mint,
-        associated_token::token_program = token_program,
+        mut,
+        token::authority = recipient,
+        token::mint = input_token_mint,
+        token::token_program = input_token_program
     )]
-    signer_input_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    recipient_input_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::authority = pool,
         associated_token::mint = input_token_mint,
-        associated_token::token_program = token_program,
+        associated_token::token_program = input_token_program,
     )]
     pool_input_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
-        mint::token_program = token_program,
+        mint::token_program = output_token_program,
         mint::authority = pool,
         mint::freeze_authority = pool,
         address = pool.output_token_mint
@@ -43,9 +45,9 @@ pub struct WithdrawStake<'info> {
     output_token_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         mut,
-        associated_token::authority = signer,
-        associated_token::mint = output_token_mint,
-        associated_token::token_program = token_program
+        token::authority = signer,
+        token::mint = output_token_mint,
+        token::token_program = output_token_program
     )]
     signer_output_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
@@ -55,6 +57,26 @@ pub struct WithdrawStake<'info> {
         bump = pool.bump
     )]
     pool: Box<Account<'info, LRTPool>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + HolderCheckpoint::INIT_SPACE,
+        seeds = [
+            b"holder_checkpoint",
+            pool.key().as_ref(),
+            signer_output_token_vault.key().as_ref()
+        ],
+        bump
+    )]
+    signer_checkpoint: Box<Account<'info, HolderCheckpoint>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserPosition::INIT_SPACE,
+        seeds = [b"user_position", pool.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    user_position: Box<Account<'info, UserPosition>>,
     #[account(mut)]
     avs: AccountInfo<'info>,
     #[account(
@@ -62,44 +84,103 @@ pub struct WithdrawStake<'info> {
         mint::authority = avs,
         mint::freeze_authority = avs,
         mint::decimals = input_token_mint.decimals,
-        mint::token_program = token_program
+        mint::token_program = input_token_program
     )]
     avs_token_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         mut,
         associated_token::mint = input_token_mint,
         associated_token::authority = avs,
-        associated_token::token_program = token_program
+        associated_token::token_program = input_token_program
     )]
     avs_input_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::authority = pool,
         associated_token::mint = avs_token_mint,
-        associated_token::token_program = token_program
+        associated_token::token_program = input_token_program
     )]
     pool_avs_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + AvsPoints::INIT_SPACE,
+        seeds = [b"avs_points", pool.key().as_ref(), avs.key().as_ref()],
+        bump
+    )]
+    avs_points: Box<Account<'info, AvsPoints>>,
     avs_program: AccountInfo<'info>,
+    // only an avs added through a timelocked AddAvs change can receive the pool's signature
+    #[account(
+        seeds = [
+            b"avs_allowlist",
+            pool.key().as_ref(),
+            avs.key().as_ref(),
+            avs_program.key().as_ref()
+        ],
+        bump = avs_allowlist.bump
+    )]
+    avs_allowlist: Box<Account<'info, AvsAllowlist>>,
     associated_token_program: Program<'info, AssociatedToken>,
-    token_program: Interface<'info, TokenInterface>,
+    input_token_program: Interface<'info, TokenInterface>,
+    output_token_program: Interface<'info, TokenInterface>,
     system_program: Program<'info, System>,
 }
 
 impl<'info> WithdrawStake<'info> {
+    // vested rewards are recognized before converting at the exchange rate, and points
+    // accrue before the supply and the total assets change
+    pub fn accrue_rewards(&mut self) -> Result<()> {
+        let supply = self.output_token_mint.supply;
+        let clock = Clock::get()?;
+        self.pool.accrue_points(supply, clock.unix_timestamp)?;
+        self.pool.accrue_rewards(supply, &clock)?;
+        Ok(())
+    }
+
+    // output tokens minted or received in their holding period cannot be burned yet
+    pub fn check_holding_period(&self, amount: u64) -> Result<()> {
+        self.signer_checkpoint.check_unlocked(
+            self.signer_output_token_vault.amount,
+            amount,
+            self.pool.min_holding_slots,
+            Clock::get()?.slot,
+        )
+    }
+
+    // settles the signer's rewards and points before burning changes its balance
+    pub fn accrue_holder_rewards(&mut self, checkpoint_bump: u8) -> Result<()> {
+        let pool_key = self.pool.key();
+        let token_account = self.signer_output_token_vault.key();
+        let now = Clock::get()?.unix_timestamp;
+        self.signer_checkpoint.init_if_needed(
+            checkpoint_bump,
+            pool_key,
+            token_account,
+            &mut self.pool,
+            self.signer_output_token_vault.amount,
+            now,
+        )?;
+        self.signer_checkpoint
+            .accrue(&self.pool, self.signer_output_token_vault.amount, now)
+    }
+
     pub fn burn_output_token(&mut self, amount: u64) -> Result<()> {
         let ctx = CpiContext::new(
-            self.token_program.to_account_info(),
+            self.output_token_program.to_account_info(),
             Burn {
                 mint: self.output_token_mint.to_account_info(),
                 from: self.signer_output_token_vault.to_account_info(),
                 authority: self.signer.to_account_info(),
             },
         );
-        burn(ctx, amount)
+        burn(ctx, amount)?;
+        self.pool.untrack_supply(amount);
+        Ok(())
     }
 
     // this undelegate method is specific to solayer endo avs program for now
-    pub fn undelegate(&mut self, amount: u64) -> Result<()> {
+    pub fn undelegate(&mut self, amount: u64, avs_points_bump: u8) -> Result<()> {
         self.pool_avs_token_vault.reload()?;
         if self.pool_avs_token_vault.amount < amount {
             return Err(LRTPoolError::InsufficientAvsTokenForUndelegate.into());
@@ -124,7 +205,7 @@ impl<'info> WithdrawStake<'info> {
             // stakerAvsTokenAccount
             AccountMeta::new(self.pool_avs_token_vault.key(), false),
             // tokenProgram
-            AccountMeta::new_readonly(self.token_program.key(), false),
+            AccountMeta::new_readonly(self.input_token_program.key(), false),
             // associatedTokenProgram
             AccountMeta::new_readonly(self.associated_token_program.key(), false),
             // systemProgram
@@ -144,6 +225,8 @@ impl<'info> WithdrawStake<'info> {
             &bump,
         ][..]];
 
+        let avs_balance_before = self.pool_avs_token_vault.amount;
+
         invoke_signed(
             &delegate_inst,
             &[
@@ -154,18 +237,40 @@ impl<'info> WithdrawStake<'info> {
                 self.input_token_mint.to_account_info(),
                 self.pool_input_token_vault.to_account_info(),
                 self.pool_avs_token_vault.to_account_info(),
-                self.token_program.to_account_info(),
+                self.input_token_program.to_account_info(),
                 self.associated_token_program.to_account_info(),
                 self.system_program.to_account_info(),
             ],
             &signer_seeds,
-        )
-        .map_err(Into::into)
+        )?;
+
+        // points were accrued at the current multiplier by accrue_rewards, the boost follows
+        // the avs tokens the pool actually gave back whatever avs program was passed
+        self.pool_avs_token_vault.reload()?;
+        let undelegated_amount = avs_balance_before
+            .checked_sub(self.pool_avs_token_vault.amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        let pool_key = self.pool.key();
+        let avs = self.avs.key();
+        self.avs_points
+            .init_if_needed(avs_points_bump, pool_key, avs);
+        let (boost_before, boost_after) = self.avs_points.undelegate(undelegated_amount);
+        self.pool.update_avs_points_boost(boost_before, boost_after)
     }
 
-    pub fn unstake(&mut self, amount: u64) -> Result<()> {
+    // returns the amount the user actually received, which is less than amount
+    // when the input token mint charges a transfer fee
+    pub fn unstake(&mut self, amount: u64) -> Result<u64> {
+        let balance_before = self.recipient_input_token_vault.amount;
+
+        // referral rewards in the vault belong to the referrers
         self.pool_input_token_vault.reload()?;
-        if self.pool_input_token_vault.amount < amount {
+        if self
+            .pool_input_token_vault
+            .amount
+            .saturating_sub(self.pool.unclaimed_referral_rewards)
+            < amount
+        {
             return Err(LRTPoolError::InsufficientSSOLFundsForWithdraw.into());
         }
 
@@ -177,25 +282,71 @@ impl<'info> WithdrawStake<'info> {
         ][..]];
 
         let ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
+            self.input_token_program.to_account_info(),
             TransferChecked {
                 from: self.pool_input_token_vault.to_account_info(),
-                to: self.signer_input_token_vault.to_account_info(),
+                to: self.recipient_input_token_vault.to_account_info(),
                 mint: self.input_token_mint.to_account_info(),
                 authority: self.pool.to_account_info(),
             },
             &signer_seeds,
         );
 
-        transfer_checked(
-            ctx,
-            self.calculate_input_token_amount(amount),
-            self.input_token_mint.decimals,
-        )
+        transfer_checked(ctx, amount, self.input_token_mint.decimals)?;
+
+        self.recipient_input_token_vault.reload()?;
+        self.recipient_input_token_vault
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(LRTPoolError::MathOverflow.into())
+    }
+
+    // positions of users who deposited before positions were tracked are created here
+    pub fn update_user_position(
+        &mut self,
+        input_amount: u64,
+        burned_amount: u64,
+        user_position_bump: u8,
+    ) -> Result<()> {
+        let pool = self.pool.key();
+        let user = self.signer.key();
+        self.user_position
+            .init_if_needed(user_position_bump, pool, user);
+        self.user_position
+            .record_withdrawal(input_amount, burned_amount)
+    }
+
+    pub fn emit_withdrawn(&self, burned_amount: u64, output_amount: u64) {
+        emit!(DelegatedStakeWithdrawn {
+            pool: self.pool.key(),
+            user: self.signer.key(),
+            recipient: self.recipient.key(),
+            avs: self.avs.key(),
+            burned_amount,
+            output_amount,
+            exchange_rate: self.pool.exchange_rate(),
+        });
+    }
+
+    // converts at the pool's exchange rate, adjust according to your business logic
+    pub fn calculate_input_token_amount(&self, amount: u64) -> Result<u64> {
+        let supply = self.output_token_mint.supply;
+        if supply == 0 {
+            return Ok(amount);
+        }
+        u64::try_from(amount as u128 * self.pool.total_assets as u128 / supply as u128)
+            .map_err(|_| LRTPoolError::MathOverflow.into())
     }
 
-    // fill this function according to your business logic
-    pub fn calculate_input_token_amount(&self, amount: u64) -> u64 {
-        amount
+    pub fn sub_total_assets(&mut self, amount: u64) -> Result<()> {
+        self.pool.total_assets = self
+            .pool
+            .total_assets
+            .checked_sub(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        self.output_token_mint.reload()?;
+        self.pool
+            .refresh_exchange_rate(self.output_token_mint.supply);
+        Ok(())
     }
 }
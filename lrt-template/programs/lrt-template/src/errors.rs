@@ -18,5 +18,131 @@ pub enum LRTPoolError {
     InsufficientAvsTokenForUndelegate,
 
     #[msg("Missing necessary accounts")]
-    MissingAccounts
+    MissingAccounts,
+
+    #[msg("Signer is not the pending delegate authority")]
+    InvalidPendingDelegateAuthority,
+
+    #[msg("Signer is not the pending admin")]
+    InvalidPendingAdmin,
+
+    #[msg("Timelock delay must not be negative")]
+    InvalidTimelockDelay,
+
+    #[msg("Timelock delay has not elapsed yet")]
+    TimelockNotElapsed,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Multisig owners must be unique and between 1 and 10")]
+    InvalidMultisigOwners,
+
+    #[msg("Multisig threshold must be between 1 and the number of owners")]
+    InvalidMultisigThreshold,
+
+    #[msg("Signer is not an owner of the multisig")]
+    NotMultisigOwner,
+
+    #[msg("Multisig owners changed after the proposal was created")]
+    StaleMultisigProposal,
+
+    #[msg("Multisig proposal has already been executed")]
+    MultisigProposalAlreadyExecuted,
+
+    #[msg("Multisig proposal does not have enough approvals")]
+    NotEnoughMultisigApprovals,
+
+    #[msg("Pool is already on the current version")]
+    PoolAlreadyMigrated,
+
+    #[msg("Pool account layout is not recognized")]
+    UnknownPoolLayout,
+
+    #[msg("Signer is not allowed to perform this action")]
+    Unauthorized,
+
+    #[msg("Output token name, symbol or uri is too long")]
+    InvalidTokenMetadata,
+
+    #[msg("Input token mint has extensions or authorities the admin did not accept")]
+    UnsafeInputTokenMint,
+
+    #[msg("Input token mints with a transfer hook program or frozen default account state are not supported")]
+    UnsupportedInputTokenMint,
+
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+
+    #[msg("Depositor is not on the pool's allowlist")]
+    NotAllowlisted,
+
+    #[msg("Invalid deposit caps")]
+    InvalidDepositCaps,
+
+    #[msg("Deposit exceeds the pool's deposit cap")]
+    DepositCapExceeded,
+
+    #[msg("Deposit exceeds the user deposit cap")]
+    UserDepositCapExceeded,
+
+    #[msg("Invalid fees")]
+    InvalidFees,
+
+    #[msg("Invalid rate limits")]
+    InvalidRateLimits,
+
+    #[msg("Depositors cannot refer themselves")]
+    SelfReferral,
+
+    #[msg("Deposits must credit the referrer recorded on the user position")]
+    ReferrerMismatch,
+
+    #[msg("Output tokens are still in their holding period")]
+    HoldingPeriodNotElapsed,
+
+    #[msg("A rate snapshot was already recorded this epoch")]
+    RateSnapshotTooEarly,
+
+    #[msg("Rate history is empty")]
+    RateHistoryEmpty,
+
+    #[msg("Invalid TWAP window")]
+    InvalidTwapWindow,
+
+    #[msg("Invalid reward vesting period")]
+    InvalidVestingPeriod,
+
+    #[msg("The pool already distributes the maximum number of reward tokens")]
+    TooManyRewardTokens,
+
+    #[msg("Unknown reward token")]
+    UnknownRewardToken,
+
+    #[msg("Transfer hook called outside of a transfer")]
+    NotTransferring,
+
+    #[msg("Invalid distribution")]
+    InvalidDistribution,
+
+    #[msg("Distribution allocation already claimed")]
+    DistributionAlreadyClaimed,
+
+    #[msg("Distribution claim deadline has passed")]
+    DistributionExpired,
+
+    #[msg("Distribution can be claimed until its deadline")]
+    DistributionNotExpired,
+
+    #[msg("Points multipliers cannot be below 1x")]
+    InvalidPointsMultiplier,
+
+    #[msg("AVS points account does not match the change")]
+    InvalidAvsPoints,
+
+    #[msg("The pool's output token mint has no transfer hook")]
+    TransferHookRequired,
+
+    #[msg("AVS allowlist account does not match the change")]
+    InvalidAvsAllowlist,
 }
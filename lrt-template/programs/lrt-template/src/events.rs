@@ -0,0 +1,288 @@
+use anchor_lang::prelude::*;
+
+use crate::state::ConfigChange;
+
+// every event carries the exchange rate after the operation,
+// see LRTPool::exchange_rate for the scale
+
+#[event]
+pub struct PoolInitialized {
+    pub pool: Pubkey,
+    pub input_token_mint: Pubkey,
+    pub output_token_mint: Pubkey,
+    pub delegate_authority: Pubkey,
+    pub admin: Pubkey,
+    pub guardian: Pubkey,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct Deposited {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub recipient: Pubkey,
+    pub referrer: Option<Pubkey>,
+    pub input_amount: u64,
+    pub fee_amount: u64,
+    pub minted_amount: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub recipient: Pubkey,
+    pub burned_amount: u64,
+    pub output_amount: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct DelegatedStakeWithdrawn {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub recipient: Pubkey,
+    pub avs: Pubkey,
+    pub burned_amount: u64,
+    pub output_amount: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct Delegated {
+    pub pool: Pubkey,
+    pub avs: Pubkey,
+    pub amount: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct Undelegated {
+    pub pool: Pubkey,
+    pub avs: Pubkey,
+    pub amount: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct DelegateAuthorityTransferred {
+    pub pool: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct DelegateAuthorityProposalCancelled {
+    pub pool: Pubkey,
+    pub proposed_authority: Option<Pubkey>,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct DelegateAuthorityRevoked {
+    pub pool: Pubkey,
+    pub old_authority: Pubkey,
+    pub revoked_by: Pubkey,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub pool: Pubkey,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct AllowlistEntryAdded {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct AllowlistEntryRemoved {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub pool: Pubkey,
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct RewardsHarvested {
+    pub pool: Pubkey,
+    pub avs: Pubkey,
+    pub reward_token_mint: Pubkey,
+    pub amount: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct HolderRewardsClaimed {
+    pub pool: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub reward_token_mint: Pubkey,
+    pub amount: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct PointsSynced {
+    pub pool: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub points: u128,
+    pub total_points: u128,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct DistributionPublished {
+    pub pool: Pubkey,
+    pub distribution: Pubkey,
+    pub reward_token_mint: Pubkey,
+    pub epoch: u64,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub num_claims: u32,
+    pub claim_deadline: i64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct DistributionClaimed {
+    pub pool: Pubkey,
+    pub distribution: Pubkey,
+    pub user: Pubkey,
+    pub index: u32,
+    pub amount: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct DistributionClosed {
+    pub pool: Pubkey,
+    pub distribution: Pubkey,
+    pub unclaimed_amount: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct TotalAssetsUpdated {
+    pub pool: Pubkey,
+    pub recognized_amount: u64,
+    pub deferred_amount: u64,
+    pub total_assets: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct RateSnapshotRecorded {
+    pub pool: Pubkey,
+    pub slot: u64,
+    pub epoch: u64,
+    pub total_assets: u64,
+    pub supply: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct HolderFrozen {
+    pub pool: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub authority: Pubkey,
+    pub reason: u16,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct HolderThawed {
+    pub pool: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub authority: Pubkey,
+    pub reason: u16,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct PoolMigrated {
+    pub pool: Pubkey,
+    pub version: u8,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct ChangeQueued {
+    pub pool: Pubkey,
+    pub pending_change: Pubkey,
+    pub change: ConfigChange,
+    pub eta: i64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct ChangeExecuted {
+    pub pool: Pubkey,
+    pub pending_change: Pubkey,
+    pub change: ConfigChange,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct ChangeCancelled {
+    pub pool: Pubkey,
+    pub pending_change: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub exchange_rate: u64,
+}
+
+// multisigs are not tied to a pool and carry no exchange rate
+
+#[event]
+pub struct MultisigCreated {
+    pub multisig: Pubkey,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct MultisigOwnersSet {
+    pub multisig: Pubkey,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub owner_set_seqno: u32,
+}
+
+#[event]
+pub struct TransactionProposed {
+    pub multisig: Pubkey,
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+}
+
+#[event]
+pub struct TransactionApproved {
+    pub multisig: Pubkey,
+    pub proposal: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct TransactionExecuted {
+    pub multisig: Pubkey,
+    pub proposal: Pubkey,
+}
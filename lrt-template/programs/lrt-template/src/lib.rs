@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
 use contexts::*;
+use spl_transfer_hook_interface::instruction::TransferHookInstruction;
+use state::{ConfigChange, DepositCaps, ProposalAccount};
 
 mod contexts;
 mod errors;
+mod events;
 mod state;
 mod utils;
 
@@ -12,55 +15,297 @@ declare_id!("Be419vzFciNeDWrX61Wwo2pqHWeX1JQVRQrwgoK6Lur2");
 pub mod lrt_template {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        ctx.accounts.initialize(ctx.bumps)
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        timelock_delay: i64,
+        name: String,
+        symbol: String,
+        uri: String,
+        accepted_input_mint_risks: u8,
+        deposit_caps: DepositCaps,
+    ) -> Result<()> {
+        ctx.accounts.initialize(
+            timelock_delay,
+            name,
+            symbol,
+            uri,
+            accepted_input_mint_risks,
+            deposit_caps,
+            ctx.bumps,
+        )
     }
 
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        // gated pools only accept allowlisted depositors
+        ctx.accounts.check_allowlist()?;
+        ctx.accounts.accrue_rewards()?;
         // transfer input token into the pool
-        ctx.accounts.stake(amount)?;
-        // calculate mint amount
-        let mint_amount = ctx.accounts.calculate_output_token_amount(amount);
+        let received_amount = ctx.accounts.stake(amount)?;
+        // the deposit fee stays in the pool, minus the referrer's share
+        let (fee_amount, referral_amount) = ctx.accounts.take_deposit_fee(received_amount)?;
+        // calculate mint amount on what the pool received, net of transfer and deposit fees
+        let mint_amount = ctx
+            .accounts
+            .calculate_output_token_amount(received_amount - fee_amount)?;
+        ctx.accounts
+            .accrue_holder_rewards(ctx.bumps.recipient_checkpoint)?;
         // mint output token
         ctx.accounts.mint_output_token(mint_amount)?;
+        ctx.accounts.lock_output_token(mint_amount)?;
+        ctx.accounts
+            .add_total_assets(received_amount - referral_amount)?;
+        ctx.accounts
+            .update_user_position(received_amount, mint_amount, ctx.bumps.user_position)?;
+        ctx.accounts
+            .emit_deposited(received_amount, fee_amount, mint_amount);
         Ok(())
     }
 
     pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
-        // burn output token from user first
+        // no withdrawals right after a deposit, against flash deposits around rate updates
+        ctx.accounts.check_holding_period(amount)?;
+        ctx.accounts.accrue_rewards()?;
+        // calculate withdraw amount before the burn changes the exchange rate inputs
+        let withdraw_amount = ctx.accounts.calculate_input_token_amount(amount)?;
+        ctx.accounts
+            .accrue_holder_rewards(ctx.bumps.signer_checkpoint)?;
+        // burn output token from user
         ctx.accounts.burn_output_token(amount)?;
-        // calculate withdraw amount
-        let withdraw_amount = ctx.accounts.calculate_input_token_amount(amount);
         // transfer input token back to user's vault
-        ctx.accounts.unstake(withdraw_amount)?;
+        let received_amount = ctx.accounts.unstake(withdraw_amount)?;
+        ctx.accounts.sub_total_assets(withdraw_amount)?;
+        ctx.accounts
+            .update_user_position(withdraw_amount, amount, ctx.bumps.user_position)?;
+        ctx.accounts.emit_withdrawn(amount, received_amount);
         Ok(())
     }
 
     // user can always withdraw stake to get sSol back even if there is no sSol liquidity in the pool
     pub fn withdraw_delegated_stake(ctx: Context<WithdrawStake>, amount: u64) -> Result<()> {
-        // burn output token from user first
+        // no withdrawals right after a deposit, against flash deposits around rate updates
+        ctx.accounts.check_holding_period(amount)?;
+        ctx.accounts.accrue_rewards()?;
+        // calculate withdraw amount before the burn changes the exchange rate inputs
+        let withdraw_amount = ctx.accounts.calculate_input_token_amount(amount)?;
+        ctx.accounts
+            .accrue_holder_rewards(ctx.bumps.signer_checkpoint)?;
+        // burn output token from user
         ctx.accounts.burn_output_token(amount)?;
-        // calculate withdraw amount
-        let withdraw_amount = ctx.accounts.calculate_input_token_amount(amount);
         // undelegate avs token
-        ctx.accounts.undelegate(withdraw_amount)?;
+        ctx.accounts
+            .undelegate(withdraw_amount, ctx.bumps.avs_points)?;
         // transfer input token back to user's vault
-        ctx.accounts.unstake(withdraw_amount)?;
+        let received_amount = ctx.accounts.unstake(withdraw_amount)?;
+        ctx.accounts.sub_total_assets(withdraw_amount)?;
+        ctx.accounts
+            .update_user_position(withdraw_amount, amount, ctx.bumps.user_position)?;
+        ctx.accounts.emit_withdrawn(amount, received_amount);
         Ok(())
     }
 
-    pub fn transfer_delegate_authority(ctx: Context<TransferDelegateAuthority>) -> Result<()> {
-        ctx.accounts.transfer_authority()?;
+    pub fn accept_authority(ctx: Context<AcceptDelegateAuthority>) -> Result<()> {
+        ctx.accounts.accept_authority()?;
+        Ok(())
+    }
+
+    pub fn cancel_proposal(ctx: Context<CancelDelegateAuthority>) -> Result<()> {
+        ctx.accounts.cancel_proposal()?;
+        Ok(())
+    }
+
+    // lets the admin or the guardian cut off a compromised delegate authority at once
+    pub fn revoke_delegate_authority(ctx: Context<RevokeDelegateAuthority>) -> Result<()> {
+        ctx.accounts.revoke_delegate_authority()
+    }
+
+    // gated-access mode for permissioned pools, toggled through the timelock
+    pub fn add_to_allowlist(ctx: Context<AllowlistUser>, user: Pubkey) -> Result<()> {
+        ctx.accounts.add_to_allowlist(user, ctx.bumps)
+    }
+
+    pub fn remove_from_allowlist(ctx: Context<RemoveAllowlistUser>) -> Result<()> {
+        ctx.accounts.remove_from_allowlist()
+    }
+
+    pub fn join_allowlist(ctx: Context<JoinAllowlist>, proof: Vec<[u8; 32]>) -> Result<()> {
+        ctx.accounts.join_allowlist(proof, ctx.bumps)
+    }
+
+    // partner program, referrers earn a share of the deposit fee
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        ctx.accounts.register_referrer(ctx.bumps)
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        ctx.accounts.claim_referral_rewards()
+    }
+
+    // claims avs rewards into the pool, input token rewards vest into the exchange rate
+    pub fn harvest(ctx: Context<Harvest>) -> Result<()> {
+        ctx.accounts.harvest(ctx.bumps)
+    }
+
+    // reward recognition, limited in how fast it may move the exchange rate
+    pub fn update_total_assets(ctx: Context<UpdateTotalAssets>) -> Result<()> {
+        ctx.accounts.update_total_assets()
+    }
+
+    // exchange rate history for apy and twap
+    pub fn record_rate_snapshot(ctx: Context<RecordRateSnapshot>) -> Result<()> {
+        ctx.accounts.record_rate_snapshot(ctx.bumps)
+    }
+
+    pub fn twap_exchange_rate(ctx: Context<ViewRateHistory>, window: i64) -> Result<u64> {
+        ctx.accounts.twap_exchange_rate(window)
+    }
+
+    // non-input reward tokens, distributed per output token held
+    pub fn open_holder_checkpoint(ctx: Context<OpenHolderCheckpoint>) -> Result<()> {
+        ctx.accounts.open_holder_checkpoint(ctx.bumps)
+    }
+
+    pub fn claim_holder_rewards(ctx: Context<ClaimHolderRewards>) -> Result<()> {
+        ctx.accounts.claim_holder_rewards()
+    }
+
+    // time-weighted restaking points, boosted by the multipliers of the avs delegated to
+    pub fn sync_points(ctx: Context<SyncPoints>) -> Result<()> {
+        ctx.accounts.sync_points()
+    }
+
+    pub fn total_points(ctx: Context<ViewPoints>) -> Result<u128> {
+        ctx.accounts.total_points()
+    }
+
+    // merkle distributor for off-chain computed reward allocations
+    pub fn publish_distribution(
+        ctx: Context<PublishDistribution>,
+        epoch: u64,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        num_claims: u32,
+        claim_deadline: i64,
+    ) -> Result<()> {
+        ctx.accounts.publish_distribution(
+            epoch,
+            merkle_root,
+            total_amount,
+            num_claims,
+            claim_deadline,
+            ctx.bumps,
+        )
+    }
+
+    pub fn claim_distribution(
+        ctx: Context<ClaimDistribution>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.claim_distribution(index, amount, proof)
+    }
+
+    pub fn close_distribution(ctx: Context<CloseDistribution>) -> Result<()> {
+        ctx.accounts.close_distribution()
+    }
+
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        ctx.accounts.transfer_hook(amount)
+    }
+
+    // the token program calls the transfer hook with the spl execute instruction,
+    // which anchor does not dispatch on its own
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        match TransferHookInstruction::unpack(data)? {
+            TransferHookInstruction::Execute { amount } => {
+                __private::__global::transfer_hook(program_id, accounts, &amount.to_le_bytes())
+            }
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }
+    }
+
+    // compliance controls on LRT token accounts through the pool's freeze authority
+    pub fn freeze_holder(ctx: Context<FreezeHolder>, reason: u16) -> Result<()> {
+        ctx.accounts.freeze(reason)
+    }
+
+    pub fn thaw_holder(ctx: Context<FreezeHolder>, reason: u16) -> Result<()> {
+        ctx.accounts.thaw(reason)
+    }
+
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        ctx.accounts.migrate_pool()
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()?;
+        Ok(())
+    }
+
+    // sensitive configuration changes only apply once the pool's timelock delay has elapsed
+    pub fn queue_change(ctx: Context<QueueChange>, change: ConfigChange) -> Result<()> {
+        ctx.accounts.queue_change(change, ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn execute_change(ctx: Context<ExecuteChange>) -> Result<()> {
+        ctx.accounts.execute_change()?;
+        Ok(())
+    }
+
+    pub fn cancel_change(ctx: Context<CancelChange>) -> Result<()> {
+        ctx.accounts.cancel_change()?;
         Ok(())
     }
 
     pub fn delegate(ctx: Context<Delegate>, amount: u64) -> Result<()> {
-        ctx.accounts.delegate(amount)?;
+        ctx.accounts.delegate(amount, ctx.bumps.avs_points)?;
         Ok(())
     }
 
     pub fn undelegate(ctx: Context<Delegate>, amount: u64) -> Result<()> {
-        ctx.accounts.undelegate(amount)?;
+        ctx.accounts.undelegate(amount, ctx.bumps.avs_points)?;
         Ok(())
     }
+
+    // M-of-N signer sets whose signer PDA can act as a pool's delegate authority or admin
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        ctx.accounts.create_multisig(owners, threshold, ctx.bumps)
+    }
+
+    pub fn set_multisig_owners(
+        ctx: Context<SetMultisigOwners>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        ctx.accounts.set_owners(owners, threshold)
+    }
+
+    pub fn propose_transaction(
+        ctx: Context<ProposeTransaction>,
+        program_id: Pubkey,
+        accounts: Vec<ProposalAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts
+            .propose_transaction(program_id, accounts, data, ctx.bumps)
+    }
+
+    pub fn approve_transaction(ctx: Context<ApproveTransaction>) -> Result<()> {
+        ctx.accounts.approve_transaction()
+    }
+
+    pub fn execute_transaction<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteTransaction<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.execute_transaction(ctx.remaining_accounts)
+    }
 }
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InitSpace;
+
+// issued by the admin or claimed with a merkle proof, required to deposit while
+// the pool's allowlist is enabled
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistEntry {
+    pub bump: u8,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+}
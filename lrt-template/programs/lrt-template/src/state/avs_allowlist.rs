@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InitSpace;
+
+// an avs and its program the pool may delegate to and sign CPIs for, only created by a
+// timelocked AddAvs change
+#[account]
+#[derive(InitSpace)]
+pub struct AvsAllowlist {
+    pub bump: u8,
+    pub pool: Pubkey,
+    pub avs: Pubkey,
+    pub avs_program: Pubkey,
+}
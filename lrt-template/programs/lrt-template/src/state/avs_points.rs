@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InitSpace;
+
+use super::BPS_DENOMINATOR;
+use crate::errors::LRTPoolError;
+
+// points multiplier of one avs and the input tokens the pool delegated to it
+#[account]
+#[derive(InitSpace)]
+pub struct AvsPoints {
+    pub bump: u8,
+    pub pool: Pubkey,
+    pub avs: Pubkey,
+    pub multiplier_bps: u16,
+    pub delegated_amount: u64,
+}
+
+impl AvsPoints {
+    // fills in an account created by init_if_needed, avs earn 1x until the admin sets a multiplier
+    pub fn init_if_needed(&mut self, bump: u8, pool: Pubkey, avs: Pubkey) {
+        if self.pool == Pubkey::default() {
+            self.bump = bump;
+            self.pool = pool;
+            self.avs = avs;
+            self.multiplier_bps = BPS_DENOMINATOR;
+        }
+    }
+
+    // contribution to the pool's avs_points_boost
+    pub fn boost(&self) -> u128 {
+        self.delegated_amount as u128 * self.multiplier_bps.saturating_sub(BPS_DENOMINATOR) as u128
+    }
+
+    // returns the boost before and after the delegated amount changes
+    pub fn delegate(&mut self, amount: u64) -> Result<(u128, u128)> {
+        let boost_before = self.boost();
+        self.delegated_amount = self
+            .delegated_amount
+            .checked_add(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        Ok((boost_before, self.boost()))
+    }
+
+    pub fn undelegate(&mut self, amount: u64) -> (u128, u128) {
+        let boost_before = self.boost();
+        self.delegated_amount = self.delegated_amount.saturating_sub(amount);
+        (boost_before, self.boost())
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+// at most 10kb of claim bitmap, the limit of an account created by cpi
+pub const MAX_DISTRIBUTION_CLAIMS: u32 = 80_000;
+
+// off-chain computed allocations of one reward token for one distribution epoch,
+// the tokens are held in a pool owned vault until they are claimed
+#[account]
+pub struct Distribution {
+    pub bump: u8,
+    pub pool: Pubkey,
+    pub reward_token_mint: Pubkey,
+    pub epoch: u64,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub num_claims: u32,
+    // allocations can be claimed until then, afterwards the admin can close the distribution
+    pub claim_deadline: i64,
+    // one bit per leaf index
+    pub claimed_bitmap: Vec<u8>,
+}
+
+impl Distribution {
+    pub fn space(num_claims: u32) -> usize {
+        8 + 1 + 32 + 32 + 8 + 32 + 8 + 8 + 4 + 8 + 4 + Self::bitmap_len(num_claims)
+    }
+
+    pub fn bitmap_len(num_claims: u32) -> usize {
+        (num_claims as usize).div_ceil(8)
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.claimed_bitmap[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u32) {
+        self.claimed_bitmap[index as usize / 8] |= 1 << (index % 8);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitmap_marks_each_index_once() {
+        let mut distribution = Distribution {
+            bump: 0,
+            pool: Pubkey::default(),
+            reward_token_mint: Pubkey::default(),
+            epoch: 0,
+            merkle_root: [0; 32],
+            total_amount: 0,
+            claimed_amount: 0,
+            num_claims: 9,
+            claim_deadline: 0,
+            claimed_bitmap: vec![0; Distribution::bitmap_len(9)],
+        };
+        assert_eq!(distribution.claimed_bitmap.len(), 2);
+
+        distribution.set_claimed(8);
+        assert!(distribution.is_claimed(8));
+        assert!((0..8).all(|index| !distribution.is_claimed(index)));
+        distribution.set_claimed(0);
+        assert!(distribution.is_claimed(0));
+        assert_eq!(distribution.claimed_bitmap, vec![1, 1]);
+    }
+
+    #[test]
+    fn largest_distribution_fits_a_cpi_created_account() {
+        assert!(Distribution::space(MAX_DISTRIBUTION_CLAIMS) <= 10_240);
+    }
+}
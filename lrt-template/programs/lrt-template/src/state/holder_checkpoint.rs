@@ -0,0 +1,281 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InitSpace;
+
+use super::{points_of, LRTPool, MAX_REWARD_TOKENS, REWARD_PER_SHARE_PRECISION};
+use crate::errors::LRTPoolError;
+
+// reward and points checkpoints of one output token account, updated before its balance changes by
+// deposits, withdrawals and the output token transfer hook
+#[account]
+#[derive(InitSpace)]
+pub struct HolderCheckpoint {
+    pub bump: u8,
+    pub pool: Pubkey,
+    pub token_account: Pubkey,
+    // pool reward_per_share of each reward token at the last update
+    pub reward_checkpoints: [u128; MAX_REWARD_TOKENS],
+    pub accrued_rewards: [u64; MAX_REWARD_TOKENS],
+    // pool points_per_token at the last update
+    pub points_checkpoint: u128,
+    pub points: u128,
+    // output tokens still in their holding period, they can neither be burned nor moved to an
+    // account without a checkpoint. tokens deposited by others or received by transfer are
+    // locked apart from own deposits, so that others cannot extend the lock of own deposits
+    pub locked_amount: u64,
+    pub lock_slot: u64,
+    pub received_locked_amount: u64,
+    pub received_lock_slot: u64,
+}
+
+// amount locked at lock_slot if the holding period has not elapsed yet, withdrawals are
+// refused at least in the slot of the deposit
+fn active_lock(amount: u64, lock_slot: u64, min_holding_slots: u64, slot: u64) -> u64 {
+    if slot < lock_slot.saturating_add(min_holding_slots.max(1)) {
+        amount
+    } else {
+        0
+    }
+}
+
+impl HolderCheckpoint {
+    // fills in a checkpoint created by init_if_needed, it only earns rewards distributed
+    // and points accrued from now on, on the balance it is opened with and later changes
+    pub fn init_if_needed(
+        &mut self,
+        bump: u8,
+        pool_key: Pubkey,
+        token_account: Pubkey,
+        pool: &mut LRTPool,
+        balance: u64,
+        now: i64,
+    ) -> Result<()> {
+        if self.pool == Pubkey::default() {
+            pool.track_supply(balance)?;
+            self.bump = bump;
+            self.pool = pool_key;
+            self.token_account = token_account;
+            for (checkpoint, reward_token) in self
+                .reward_checkpoints
+                .iter_mut()
+                .zip(pool.reward_tokens.iter())
+            {
+                *checkpoint = reward_token.reward_per_share;
+            }
+            self.points_checkpoint = pool.points_per_token_at(now)?;
+        }
+        Ok(())
+    }
+
+    pub fn locked_amount(&self, min_holding_slots: u64, slot: u64) -> u64 {
+        active_lock(self.locked_amount, self.lock_slot, min_holding_slots, slot).saturating_add(
+            active_lock(
+                self.received_locked_amount,
+                self.received_lock_slot,
+                min_holding_slots,
+                slot,
+            ),
+        )
+    }
+
+    // locks amount from lock_slot on, together with the tokens still locked in the same bucket
+    pub fn add_lock(
+        &mut self,
+        own: bool,
+        amount: u64,
+        lock_slot: u64,
+        min_holding_slots: u64,
+        slot: u64,
+    ) -> Result<()> {
+        let (locked_amount, locked_slot) = if own {
+            (&mut self.locked_amount, &mut self.lock_slot)
+        } else {
+            (
+                &mut self.received_locked_amount,
+                &mut self.received_lock_slot,
+            )
+        };
+        let still_locked = active_lock(*locked_amount, *locked_slot, min_holding_slots, slot);
+        *locked_amount = still_locked
+            .checked_add(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        if still_locked == 0 || lock_slot > *locked_slot {
+            *locked_slot = lock_slot;
+        }
+        Ok(())
+    }
+
+    pub fn check_unlocked(
+        &self,
+        balance: u64,
+        amount: u64,
+        min_holding_slots: u64,
+        slot: u64,
+    ) -> Result<()> {
+        require!(
+            amount <= balance.saturating_sub(self.locked_amount(min_holding_slots, slot)),
+            LRTPoolError::HoldingPeriodNotElapsed
+        );
+        Ok(())
+    }
+
+    // releases the locked tokens among amount transferred out of balance, received ones first,
+    // returns how many were locked and the latest slot they were locked in
+    pub fn release_transferred_lock(
+        &mut self,
+        balance: u64,
+        amount: u64,
+        min_holding_slots: u64,
+        slot: u64,
+    ) -> (u64, u64) {
+        let unlocked = balance.saturating_sub(self.locked_amount(min_holding_slots, slot));
+        let released = amount.saturating_sub(unlocked);
+        if released == 0 {
+            return (0, 0);
+        }
+
+        let received_locked = active_lock(
+            self.received_locked_amount,
+            self.received_lock_slot,
+            min_holding_slots,
+            slot,
+        );
+        let own_locked = active_lock(self.locked_amount, self.lock_slot, min_holding_slots, slot);
+        let from_received = released.min(received_locked);
+        let from_own = released - from_received;
+        self.received_locked_amount = received_locked - from_received;
+        self.locked_amount = own_locked.saturating_sub(from_own);
+
+        let mut lock_slot = 0;
+        if from_received != 0 {
+            lock_slot = self.received_lock_slot;
+        }
+        if from_own != 0 {
+            lock_slot = lock_slot.max(self.lock_slot);
+        }
+        (released, lock_slot)
+    }
+
+    // credits the rewards and points earned by balance since the last update
+    pub fn accrue(&mut self, pool: &LRTPool, balance: u64, now: i64) -> Result<()> {
+        for (i, reward_token) in pool.reward_tokens.iter().enumerate() {
+            let earned = (balance as u128)
+                .checked_mul(reward_token.reward_per_share - self.reward_checkpoints[i])
+                .ok_or(LRTPoolError::MathOverflow)?
+                / REWARD_PER_SHARE_PRECISION;
+            self.accrued_rewards[i] = u64::try_from(earned)
+                .ok()
+                .and_then(|earned| self.accrued_rewards[i].checked_add(earned))
+                .ok_or(LRTPoolError::MathOverflow)?;
+            self.reward_checkpoints[i] = reward_token.reward_per_share;
+        }
+
+        let points_per_token = pool.points_per_token_at(now)?;
+        self.points = self
+            .points
+            .checked_add(points_of(
+                balance,
+                points_per_token - self.points_checkpoint,
+            )?)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        self.points_checkpoint = points_per_token;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::POINTS_RATE_PRECISION;
+
+    fn checkpoint() -> HolderCheckpoint {
+        HolderCheckpoint {
+            bump: 0,
+            pool: Pubkey::default(),
+            token_account: Pubkey::default(),
+            reward_checkpoints: [0; MAX_REWARD_TOKENS],
+            accrued_rewards: [0; MAX_REWARD_TOKENS],
+            points_checkpoint: 0,
+            points: 0,
+            locked_amount: 0,
+            lock_slot: 0,
+            received_locked_amount: 0,
+            received_lock_slot: 0,
+        }
+    }
+
+    #[test]
+    fn minted_tokens_are_locked_for_the_holding_period() {
+        let mut checkpoint = checkpoint();
+        checkpoint.add_lock(true, 100, 10, 5, 10).unwrap();
+        assert_eq!(checkpoint.locked_amount(5, 10), 100);
+        assert_eq!(checkpoint.locked_amount(5, 14), 100);
+        assert_eq!(checkpoint.locked_amount(5, 15), 0);
+        // without a minimum the deposit slot is still locked
+        assert_eq!(checkpoint.locked_amount(0, 10), 100);
+        assert_eq!(checkpoint.locked_amount(0, 11), 0);
+    }
+
+    #[test]
+    fn only_unlocked_tokens_can_be_burned() {
+        let mut checkpoint = checkpoint();
+        checkpoint.add_lock(true, 100, 10, 5, 10).unwrap();
+        assert!(checkpoint.check_unlocked(150, 50, 5, 12).is_ok());
+        assert!(checkpoint.check_unlocked(150, 51, 5, 12).is_err());
+        assert!(checkpoint.check_unlocked(150, 150, 5, 15).is_ok());
+    }
+
+    #[test]
+    fn received_locks_do_not_extend_own_locks() {
+        let mut checkpoint = checkpoint();
+        checkpoint.add_lock(true, 100, 10, 5, 10).unwrap();
+        checkpoint.add_lock(false, 1, 14, 5, 14).unwrap();
+        assert_eq!(checkpoint.locked_amount(5, 14), 101);
+        assert_eq!(checkpoint.locked_amount(5, 15), 1);
+        assert_eq!(checkpoint.locked_amount(5, 19), 0);
+    }
+
+    #[test]
+    fn expired_locks_are_dropped_when_adding() {
+        let mut checkpoint = checkpoint();
+        checkpoint.add_lock(true, 100, 10, 5, 10).unwrap();
+        checkpoint.add_lock(true, 20, 30, 5, 30).unwrap();
+        assert_eq!(checkpoint.locked_amount, 20);
+        assert_eq!(checkpoint.lock_slot, 30);
+    }
+
+    #[test]
+    fn transfers_release_locked_tokens_received_first() {
+        let mut checkpoint = checkpoint();
+        checkpoint.add_lock(true, 100, 10, 5, 10).unwrap();
+        checkpoint.add_lock(false, 50, 12, 5, 12).unwrap();
+
+        // unlocked tokens move first
+        assert_eq!(checkpoint.release_transferred_lock(200, 50, 5, 13), (0, 0));
+        assert_eq!(
+            checkpoint.release_transferred_lock(150, 80, 5, 13),
+            (80, 12)
+        );
+        assert_eq!(checkpoint.received_locked_amount, 0);
+        assert_eq!(checkpoint.locked_amount, 70);
+        assert_eq!(checkpoint.release_transferred_lock(70, 70, 5, 13), (70, 10));
+        assert_eq!(checkpoint.locked_amount(5, 13), 0);
+    }
+
+    #[test]
+    fn holders_accrue_points_for_their_balance() {
+        let mut pool = LRTPool::deserialize(&mut &[0u8; LRTPool::INIT_SPACE][..]).unwrap();
+        pool.points_rate = POINTS_RATE_PRECISION as u64;
+        let mut checkpoint = checkpoint();
+        checkpoint.accrue(&pool, 100, 10).unwrap();
+        assert_eq!(checkpoint.points, 1_000);
+
+        // accrues without the pool being written in between
+        checkpoint.accrue(&pool, 100, 15).unwrap();
+        assert_eq!(checkpoint.points, 1_500);
+
+        pool.accrue_points(1_000, 20).unwrap();
+        pool.points_rate = 0;
+        checkpoint.accrue(&pool, 50, 30).unwrap();
+        assert_eq!(checkpoint.points, 1_750);
+    }
+}
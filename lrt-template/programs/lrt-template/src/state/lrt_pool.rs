@@ -1,11 +1,611 @@
 use anchor_lang::prelude::*;
 use anchor_lang::InitSpace;
 
+use crate::errors::LRTPoolError;
+
 #[account]
 #[derive(InitSpace)]
 pub struct LRTPool {
+    pub bump: u8,
+    pub version: u8,
+    pub input_token_mint: Pubkey,
+    pub output_token_mint: Pubkey,
+    pub delegate_authority: Pubkey,
+    // proposed through a timelocked change and set as delegate authority once it accepts
+    pub pending_delegate_authority: Option<Pubkey>,
+    // governs pool configuration and can replace the delegate authority
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    // can cancel queued configuration changes during the timelock delay
+    pub guardian: Pubkey,
+    // seconds a queued configuration change has to wait before it can be executed
+    pub timelock_delay: i64,
+    // used to derive the address of the next pending change
+    pub change_nonce: u64,
+    // input token mint risks the admin explicitly accepted at initialize, see input_mint_risk
+    pub accepted_input_mint_risks: u8,
+    // when enabled, depositors need an AllowlistEntry issued by the admin or claimed with a
+    // merkle proof against allowlist_merkle_root
+    pub allowlist_enabled: bool,
+    pub allowlist_merkle_root: [u8; 32],
+    pub deposit_caps: DepositCaps,
+    // share of each deposit kept by the pool, in basis points of the input amount
+    pub deposit_fee_bps: u16,
+    // share of the deposit fee credited to the depositor's referrer, in basis points of the fee
+    pub referral_fee_share_bps: u16,
+    // referral rewards still held in the pool input token vault, not available for withdrawals
+    pub unclaimed_referral_rewards: u64,
+    // slots a user has to wait after a deposit before withdrawing, withdrawing in the
+    // slot of a deposit is never allowed
+    pub min_holding_slots: u64,
+    // input tokens backing the output token supply: deposits net of referral rewards,
+    // minus withdrawals
+    pub total_assets: u64,
+    // exchange rate after the last deposit, withdrawal or rate snapshot, reported in events
+    pub last_exchange_rate: u64,
+    // caps on how far rewards may raise the exchange rate within a slot and within an
+    // epoch, in basis points, 0 means unlimited. update_total_assets is permissionless, so
+    // the per-update cap is measured from the rate at the start of the slot
+    pub max_rate_increase_per_update_bps: u16,
+    pub max_rate_increase_per_epoch_bps: u16,
+    pub rate_limit_epoch: u64,
+    pub epoch_start_rate: u64,
+    // rewards above the rate limits, recognized by later updates
+    pub deferred_rewards: u64,
+    // rewards are streamed into total_assets linearly over this many seconds, so that
+    // depositing right before a harvest does not capture them
+    pub reward_vesting_period: i64,
+    pub unvested_rewards: u64,
+    pub vesting_end: i64,
+    pub last_vesting_update: i64,
+    // rewards paid in other tokens than the input token, distributed per output token
+    pub reward_tokens: [RewardToken; MAX_REWARD_TOKENS],
+    // restaking points per output token base unit and second, scaled by POINTS_RATE_PRECISION
+    pub points_rate: u64,
+    // cumulative points per output token base unit, scaled by POINTS_RATE_PRECISION and bps
+    pub points_per_token: u128,
+    pub total_points: u128,
+    pub last_points_update: i64,
+    // avs delegations weighted by their points multiplier above 1x, in input token bps
+    pub avs_points_boost: u128,
+    // output tokens held by accounts with a HolderCheckpoint, reward tokens are distributed
+    // over them so that nothing is owed to accounts that cannot claim
+    pub tracked_supply: u64,
+    // rewards harvested while no output tokens were tracked, added to the next distribution
+    pub undistributed_rewards: [u64; MAX_REWARD_TOKENS],
+    pub rate_limit_slot: u64,
+    pub slot_start_rate: u64,
+    // whether output token transfers call this program's transfer hook, which keeps holder
+    // checkpoints current. Pools migrated from before the hook existed have no hook
+    pub transfer_hook_enabled: bool,
+    // new fields are carved out of the reserved space so that existing pools keep their size,
+    // zeroed bytes must therefore be a valid default for every new field
+    pub reserved: [u8; POOL_RESERVED_SPACE],
+}
+
+pub const CURRENT_POOL_VERSION: u8 = 1;
+pub const POOL_RESERVED_SPACE: usize = 512;
+
+pub const BPS_DENOMINATOR: u16 = 10_000;
+pub const MAX_DEPOSIT_FEE_BPS: u16 = 1_000;
+pub const DEFAULT_MAX_RATE_INCREASE_PER_UPDATE_BPS: u16 = 10;
+pub const DEFAULT_MAX_RATE_INCREASE_PER_EPOCH_BPS: u16 = 50;
+pub const MAX_RATE_INCREASE_BPS: u16 = BPS_DENOMINATOR;
+pub const DEFAULT_REWARD_VESTING_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+pub const MAX_REWARD_TOKENS: usize = 4;
+pub const REWARD_PER_SHARE_PRECISION: u128 = 1_000_000_000_000;
+pub const POINTS_RATE_PRECISION: u128 = 1_000_000_000;
+
+// an unused slot has the default mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct RewardToken {
+    pub mint: Pubkey,
+    // cumulative rewards per output token, scaled by REWARD_PER_SHARE_PRECISION
+    pub reward_per_share: u128,
+}
+
+// caps are in input token units, 0 means unlimited
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct DepositCaps {
+    // cap on the pool's total value locked
+    pub deposit_cap: u64,
+    // cap on the net amount deposited by a single user
+    pub user_deposit_cap: u64,
+    // the tvl cap grows linearly from ramp_initial_cap to deposit_cap between
+    // ramp_start and ramp_end for a guarded launch
+    pub ramp_initial_cap: u64,
+    pub ramp_start: i64,
+    pub ramp_end: i64,
+}
+
+impl DepositCaps {
+    pub fn is_valid(&self) -> bool {
+        self.ramp_end >= self.ramp_start
+            && (self.ramp_end == self.ramp_start
+                || (self.deposit_cap != 0 && self.ramp_initial_cap <= self.deposit_cap))
+    }
+
+    // None when the pool is uncapped, a ramp cap of 0 closes the pool until it grows
+    pub fn current_deposit_cap(&self, now: i64) -> Option<u64> {
+        if self.deposit_cap == 0 {
+            return None;
+        }
+        if now >= self.ramp_end {
+            return Some(self.deposit_cap);
+        }
+        if now <= self.ramp_start {
+            return Some(self.ramp_initial_cap);
+        }
+
+        let elapsed = (now - self.ramp_start) as u128;
+        let duration = (self.ramp_end - self.ramp_start) as u128;
+        let growth = (self.deposit_cap - self.ramp_initial_cap) as u128 * elapsed / duration;
+        Some(self.ramp_initial_cap + growth as u64)
+    }
+}
+
+// mint features that let a third party claw back or freeze the pool's input tokens or replace
+// the mint, initialize rejects an input token mint with any of them unless the admin accepts it
+pub mod input_mint_risk {
+    pub const FREEZE_AUTHORITY: u8 = 1 << 0;
+    pub const PERMANENT_DELEGATE: u8 = 1 << 1;
+    // 1 << 2 accepted a transfer hook and 1 << 3 frozen-by-default token accounts, input token
+    // mints with either are no longer supported
+    pub const CLOSE_AUTHORITY: u8 = 1 << 4;
+}
+
+// pool layout before versioning, migrated in place by migrate_pool
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LRTPoolV0 {
     pub bump: u8,
     pub input_token_mint: Pubkey,
     pub output_token_mint: Pubkey,
     pub delegate_authority: Pubkey,
 }
+
+pub const EXCHANGE_RATE_PRECISION: u64 = 1_000_000_000;
+
+// input token amount backing one output token, scaled by EXCHANGE_RATE_PRECISION,
+// 1:1 while there are no assets or no output tokens
+pub fn exchange_rate_of(total_assets: u64, supply: u64) -> u64 {
+    if total_assets == 0 || supply == 0 {
+        return EXCHANGE_RATE_PRECISION;
+    }
+    let rate = total_assets as u128 * EXCHANGE_RATE_PRECISION as u128 / supply as u128;
+    u64::try_from(rate).unwrap_or(u64::MAX)
+}
+
+// points earned by balance while points_per_token grew by growth
+pub fn points_of(balance: u64, growth: u128) -> Result<u128> {
+    Ok((balance as u128)
+        .checked_mul(growth)
+        .ok_or(LRTPoolError::MathOverflow)?
+        / (POINTS_RATE_PRECISION * BPS_DENOMINATOR as u128))
+}
+
+impl LRTPool {
+    // it should agree with the calculate_*_token_amount functions of deposit and withdraw
+    pub fn exchange_rate(&self) -> u64 {
+        if self.last_exchange_rate == 0 {
+            return EXCHANGE_RATE_PRECISION;
+        }
+        self.last_exchange_rate
+    }
+
+    // instructions that see the output token mint keep the reported rate current
+    pub fn refresh_exchange_rate(&mut self, supply: u64) {
+        self.last_exchange_rate = exchange_rate_of(self.total_assets, supply);
+    }
+
+    pub fn deposit_fee(&self, amount: u64) -> u64 {
+        (amount as u128 * self.deposit_fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    // the referrer's share of a deposit fee
+    pub fn referral_fee(&self, fee_amount: u64) -> u64 {
+        (fee_amount as u128 * self.referral_fee_share_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    // starts vesting new rewards, the ones still unvested vest over the new period as well
+    pub fn vest_rewards(&mut self, amount: u64, supply: u64, clock: &Clock) -> Result<()> {
+        self.accrue_rewards(supply, clock)?;
+        self.unvested_rewards = self
+            .unvested_rewards
+            .checked_add(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        self.vesting_end = clock
+            .unix_timestamp
+            .checked_add(self.reward_vesting_period)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        // releases the rewards at once when there is no vesting period
+        self.accrue_rewards(supply, clock)?;
+        Ok(())
+    }
+
+    // slot of a reward token, registering it on first use
+    pub fn reward_token_index(&mut self, mint: Pubkey) -> Result<usize> {
+        if let Some(index) = self
+            .reward_tokens
+            .iter()
+            .position(|reward_token| reward_token.mint == mint)
+        {
+            return Ok(index);
+        }
+        let index = self
+            .reward_tokens
+            .iter()
+            .position(|reward_token| reward_token.mint == Pubkey::default())
+            .ok_or(LRTPoolError::TooManyRewardTokens)?;
+        self.reward_tokens[index].mint = mint;
+        Ok(index)
+    }
+
+    // distributes amount and what could not be distributed before over the tracked supply
+    pub fn distribute_rewards(&mut self, index: usize, amount: u64) -> Result<()> {
+        let amount = self.undistributed_rewards[index]
+            .checked_add(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        if self.tracked_supply == 0 {
+            self.undistributed_rewards[index] = amount;
+            return Ok(());
+        }
+        self.undistributed_rewards[index] = 0;
+
+        let reward_per_share =
+            amount as u128 * REWARD_PER_SHARE_PRECISION / self.tracked_supply as u128;
+        self.reward_tokens[index].reward_per_share = self.reward_tokens[index]
+            .reward_per_share
+            .checked_add(reward_per_share)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn track_supply(&mut self, amount: u64) -> Result<()> {
+        self.tracked_supply = self
+            .tracked_supply
+            .checked_add(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        Ok(())
+    }
+
+    // saturates, output tokens burned outside of the pool leave the tracked supply too high
+    pub fn untrack_supply(&mut self, amount: u64) {
+        self.tracked_supply = self.tracked_supply.saturating_sub(amount);
+    }
+
+    // recognizes the rewards vested since the last update, returns the amount recognized
+    pub fn accrue_rewards(&mut self, supply: u64, clock: &Clock) -> Result<u64> {
+        let now = clock.unix_timestamp;
+        let vested = if now >= self.vesting_end {
+            self.unvested_rewards
+        } else {
+            let elapsed = now.saturating_sub(self.last_vesting_update).max(0) as u128;
+            let remaining = (self.vesting_end - self.last_vesting_update) as u128;
+            (self.unvested_rewards as u128 * elapsed / remaining) as u64
+        };
+        self.unvested_rewards -= vested;
+        self.last_vesting_update = now;
+        self.recognize_rewards(vested, supply, clock)
+    }
+
+    // adds rewards together with previously deferred ones to total_assets as far as the
+    // rate limits allow and defers the rest, returns the amount recognized now
+    pub fn recognize_rewards(&mut self, amount: u64, supply: u64, clock: &Clock) -> Result<u64> {
+        let current_rate = exchange_rate_of(self.total_assets, supply);
+        // a zero start rate was never recorded, the first slot and epoch are 0 on a fresh pool
+        if clock.slot != self.rate_limit_slot || self.slot_start_rate == 0 {
+            self.rate_limit_slot = clock.slot;
+            self.slot_start_rate = current_rate;
+        }
+        if clock.epoch != self.rate_limit_epoch || self.epoch_start_rate == 0 {
+            self.rate_limit_epoch = clock.epoch;
+            self.epoch_start_rate = current_rate;
+        }
+
+        let pending = self
+            .deferred_rewards
+            .checked_add(amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        let mut recognized = pending;
+        // an empty pool has no holders whose rate could be inflated
+        if self.total_assets != 0 && supply != 0 {
+            if self.max_rate_increase_per_update_bps != 0 {
+                recognized = recognized.min(self.rewards_headroom(
+                    self.slot_start_rate,
+                    self.max_rate_increase_per_update_bps,
+                    supply,
+                ));
+            }
+            if self.max_rate_increase_per_epoch_bps != 0 {
+                recognized = recognized.min(self.rewards_headroom(
+                    self.epoch_start_rate,
+                    self.max_rate_increase_per_epoch_bps,
+                    supply,
+                ));
+            }
+        }
+
+        self.total_assets = self
+            .total_assets
+            .checked_add(recognized)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        self.deferred_rewards = pending - recognized;
+        self.refresh_exchange_rate(supply);
+        Ok(recognized)
+    }
+
+    // 1x plus the boost of the delegations to avs with a points multiplier, in bps
+    pub fn points_multiplier_bps(&self) -> u128 {
+        if self.total_assets == 0 {
+            return BPS_DENOMINATOR as u128;
+        }
+        BPS_DENOMINATOR as u128 + self.avs_points_boost / self.total_assets as u128
+    }
+
+    // includes the growth since the last update, so holders can accrue without writing the pool
+    pub fn points_per_token_at(&self, now: i64) -> Result<u128> {
+        self.points_per_token
+            .checked_add(self.points_growth(now)?)
+            .ok_or(LRTPoolError::MathOverflow.into())
+    }
+
+    pub fn total_points_at(&self, supply: u64, now: i64) -> Result<u128> {
+        self.total_points
+            .checked_add(points_of(supply, self.points_growth(now)?)?)
+            .ok_or(LRTPoolError::MathOverflow.into())
+    }
+
+    // called before the supply, the total assets or the points parameters change
+    pub fn accrue_points(&mut self, supply: u64, now: i64) -> Result<()> {
+        self.total_points = self.total_points_at(supply, now)?;
+        self.points_per_token = self.points_per_token_at(now)?;
+        self.last_points_update = now;
+        Ok(())
+    }
+
+    pub fn update_avs_points_boost(&mut self, boost_before: u128, boost_after: u128) -> Result<()> {
+        self.avs_points_boost = self
+            .avs_points_boost
+            .saturating_sub(boost_before)
+            .checked_add(boost_after)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        Ok(())
+    }
+
+    fn points_growth(&self, now: i64) -> Result<u128> {
+        let elapsed = now.saturating_sub(self.last_points_update).max(0) as u128;
+        elapsed
+            .checked_mul(self.points_rate as u128)
+            .and_then(|growth| growth.checked_mul(self.points_multiplier_bps()))
+            .ok_or(LRTPoolError::MathOverflow.into())
+    }
+
+    // assets that can be added before the rate exceeds base_rate raised by max_increase_bps
+    fn rewards_headroom(&self, base_rate: u64, max_increase_bps: u16, supply: u64) -> u64 {
+        let max_rate = base_rate as u128 * (BPS_DENOMINATOR as u128 + max_increase_bps as u128)
+            / BPS_DENOMINATOR as u128;
+        let max_assets = max_rate * supply as u128 / EXCHANGE_RATE_PRECISION as u128;
+        let headroom = max_assets.saturating_sub(self.total_assets as u128);
+        u64::try_from(headroom).unwrap_or(u64::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // zeroed bytes are the default of every field
+    fn pool() -> LRTPool {
+        LRTPool::deserialize(&mut &[0u8; LRTPool::INIT_SPACE][..]).unwrap()
+    }
+
+    fn ramp(ramp_initial_cap: u64) -> DepositCaps {
+        DepositCaps {
+            deposit_cap: 1_000,
+            user_deposit_cap: 0,
+            ramp_initial_cap,
+            ramp_start: 100,
+            ramp_end: 200,
+        }
+    }
+
+    #[test]
+    fn deposit_cap_ramps_linearly() {
+        let caps = ramp(100);
+        assert_eq!(caps.current_deposit_cap(0), Some(100));
+        assert_eq!(caps.current_deposit_cap(100), Some(100));
+        assert_eq!(caps.current_deposit_cap(150), Some(550));
+        assert_eq!(caps.current_deposit_cap(200), Some(1_000));
+        assert_eq!(caps.current_deposit_cap(300), Some(1_000));
+    }
+
+    #[test]
+    fn zero_ramp_caps_are_enforced() {
+        let caps = ramp(0);
+        assert_eq!(caps.current_deposit_cap(50), Some(0));
+        assert_eq!(caps.current_deposit_cap(100), Some(0));
+        assert_eq!(caps.current_deposit_cap(100 + 1), Some(10));
+
+        let small = DepositCaps {
+            deposit_cap: 10,
+            ..ramp(0)
+        };
+        assert_eq!(small.current_deposit_cap(105), Some(0));
+    }
+
+    #[test]
+    fn zero_deposit_cap_is_uncapped() {
+        let caps = DepositCaps::default();
+        assert!(caps.is_valid());
+        assert_eq!(caps.current_deposit_cap(0), None);
+    }
+
+    #[test]
+    fn rewards_are_distributed_over_the_tracked_supply() {
+        let mut pool = pool();
+        pool.distribute_rewards(0, 100).unwrap();
+        assert_eq!(pool.reward_tokens[0].reward_per_share, 0);
+        assert_eq!(pool.undistributed_rewards[0], 100);
+
+        pool.track_supply(1_000).unwrap();
+        pool.distribute_rewards(0, 400).unwrap();
+        assert_eq!(pool.undistributed_rewards[0], 0);
+        assert_eq!(
+            pool.reward_tokens[0].reward_per_share,
+            REWARD_PER_SHARE_PRECISION / 2
+        );
+
+        pool.untrack_supply(2_000);
+        assert_eq!(pool.tracked_supply, 0);
+    }
+
+    #[test]
+    fn rewards_headroom_is_bounded_by_the_increase() {
+        let mut pool = pool();
+        pool.total_assets = 1_000_000;
+        let rate = exchange_rate_of(pool.total_assets, 1_000_000);
+        assert_eq!(pool.rewards_headroom(rate, 10, 1_000_000), 1_000);
+        assert_eq!(pool.rewards_headroom(rate, 0, 1_000_000), 0);
+    }
+
+    #[test]
+    fn rewards_headroom_does_not_overflow_large_increases() {
+        let mut pool = pool();
+        pool.total_assets = 1_000_000;
+        let rate = exchange_rate_of(pool.total_assets, 1_000_000);
+        assert_eq!(pool.rewards_headroom(rate, u16::MAX, 1_000_000), 6_553_500);
+    }
+
+    fn clock(unix_timestamp: i64, slot: u64) -> Clock {
+        Clock {
+            unix_timestamp,
+            slot,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn rewards_vest_linearly() {
+        let mut pool = pool();
+        pool.total_assets = 1_000;
+        pool.reward_vesting_period = 100;
+        pool.vest_rewards(100, 1_000, &clock(0, 0)).unwrap();
+        assert_eq!(pool.unvested_rewards, 100);
+        assert_eq!(pool.total_assets, 1_000);
+
+        assert_eq!(pool.accrue_rewards(1_000, &clock(25, 0)).unwrap(), 25);
+        assert_eq!(pool.accrue_rewards(1_000, &clock(50, 0)).unwrap(), 25);
+        assert_eq!(pool.total_assets, 1_050);
+        assert_eq!(pool.exchange_rate(), 1_050_000_000);
+
+        assert_eq!(pool.accrue_rewards(1_000, &clock(500, 0)).unwrap(), 50);
+        assert_eq!(pool.unvested_rewards, 0);
+        assert_eq!(pool.total_assets, 1_100);
+    }
+
+    #[test]
+    fn new_rewards_restart_the_vesting_schedule() {
+        let mut pool = pool();
+        pool.total_assets = 1_000;
+        pool.reward_vesting_period = 100;
+        pool.vest_rewards(100, 1_000, &clock(0, 0)).unwrap();
+
+        // the 50 still unvested vest together with the new 50 until 150
+        pool.vest_rewards(50, 1_000, &clock(50, 0)).unwrap();
+        assert_eq!(pool.total_assets, 1_050);
+        assert_eq!(pool.unvested_rewards, 100);
+        assert_eq!(pool.vesting_end, 150);
+        assert_eq!(pool.accrue_rewards(1_000, &clock(100, 0)).unwrap(), 50);
+    }
+
+    #[test]
+    fn rewards_vest_at_once_without_a_vesting_period() {
+        let mut pool = pool();
+        pool.total_assets = 1_000;
+        pool.vest_rewards(100, 1_000, &clock(10, 0)).unwrap();
+        assert_eq!(pool.unvested_rewards, 0);
+        assert_eq!(pool.total_assets, 1_100);
+    }
+
+    #[test]
+    fn vested_rewards_above_the_rate_limits_are_deferred() {
+        let mut pool = pool();
+        pool.total_assets = 1_000_000;
+        pool.max_rate_increase_per_update_bps = 10;
+        pool.vest_rewards(5_000, 1_000_000, &clock(0, 1)).unwrap();
+        assert_eq!(pool.total_assets, 1_001_000);
+        assert_eq!(pool.deferred_rewards, 4_000);
+
+        // repeated updates in one slot share its cap
+        assert_eq!(
+            pool.recognize_rewards(0, 1_000_000, &clock(0, 1)).unwrap(),
+            0
+        );
+        assert_eq!(pool.deferred_rewards, 4_000);
+
+        assert_eq!(
+            pool.recognize_rewards(0, 1_000_000, &clock(0, 2)).unwrap(),
+            1_001
+        );
+        assert_eq!(pool.deferred_rewards, 2_999);
+    }
+
+    #[test]
+    fn the_epoch_cap_bounds_the_slots_of_an_epoch() {
+        let mut pool = pool();
+        pool.total_assets = 1_000_000;
+        pool.max_rate_increase_per_update_bps = 10;
+        pool.max_rate_increase_per_epoch_bps = 15;
+        pool.recognize_rewards(5_000, 1_000_000, &clock(0, 1))
+            .unwrap();
+        assert_eq!(
+            pool.recognize_rewards(0, 1_000_000, &clock(0, 2)).unwrap(),
+            500
+        );
+        assert_eq!(pool.total_assets, 1_001_500);
+        assert_eq!(
+            pool.recognize_rewards(0, 1_000_000, &clock(0, 3)).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn points_accrue_with_the_avs_boost() {
+        let mut pool = pool();
+        pool.points_rate = POINTS_RATE_PRECISION as u64;
+        pool.last_points_update = 10;
+
+        pool.accrue_points(1_000, 20).unwrap();
+        assert_eq!(pool.total_points, 10_000);
+        assert_eq!(points_of(1, pool.points_per_token).unwrap(), 10);
+
+        // half of the assets delegated to an avs with a 3x multiplier make the pool 2x
+        pool.total_assets = 1_000;
+        pool.update_avs_points_boost(0, 500 * 2 * BPS_DENOMINATOR as u128)
+            .unwrap();
+        assert_eq!(pool.points_multiplier_bps(), 2 * BPS_DENOMINATOR as u128);
+        assert_eq!(pool.total_points_at(1_000, 30).unwrap(), 30_000);
+        assert_eq!(
+            points_of(1, pool.points_per_token_at(30).unwrap()).unwrap(),
+            30
+        );
+    }
+
+    #[test]
+    fn deposit_fee_is_split_with_the_referrer() {
+        let mut pool = pool();
+        assert_eq!(pool.deposit_fee(1_000_000), 0);
+
+        pool.deposit_fee_bps = 50;
+        pool.referral_fee_share_bps = 2_000;
+        let fee = pool.deposit_fee(1_000_000);
+        assert_eq!(fee, 5_000);
+        assert_eq!(pool.referral_fee(fee), 1_000);
+
+        // rounds down in favor of the depositor and the pool
+        assert_eq!(pool.deposit_fee(199), 0);
+        assert_eq!(pool.referral_fee(4), 0);
+        assert_eq!(pool.deposit_fee(u64::MAX), u64::MAX / 200);
+    }
+}
@@ -1,2 +1,35 @@
 pub mod lrt_pool;
 pub use lrt_pool::*;
+
+pub mod pending_change;
+pub use pending_change::*;
+
+pub mod multisig;
+pub use multisig::*;
+
+pub mod allowlist;
+pub use allowlist::*;
+
+pub mod user_position;
+pub use user_position::*;
+
+pub mod referrer_rewards;
+pub use referrer_rewards::*;
+
+pub mod rate_history;
+pub use rate_history::*;
+
+pub mod reward_vault;
+pub use reward_vault::*;
+
+pub mod holder_checkpoint;
+pub use holder_checkpoint::*;
+
+pub mod distribution;
+pub use distribution::*;
+
+pub mod avs_points;
+pub use avs_points::*;
+
+pub mod avs_allowlist;
+pub use avs_allowlist::*;
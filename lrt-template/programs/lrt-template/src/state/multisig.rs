@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InitSpace;
+
+pub const MAX_MULTISIG_OWNERS: usize = 10;
+
+// the multisig signer PDA, derived from [b"multisig_signer", multisig], is the key
+// that is set as delegate authority or admin of a pool
+#[account]
+#[derive(InitSpace)]
+pub struct Multisig {
+    pub bump: u8,
+    pub signer_bump: u8,
+    pub create_key: Pubkey,
+    #[max_len(MAX_MULTISIG_OWNERS)]
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64,
+    // bumped whenever the owners change so that pending proposals can no longer execute
+    pub owner_set_seqno: u32,
+}
+
+impl Multisig {
+    pub fn owner_index(&self, key: &Pubkey) -> Option<usize> {
+        self.owners.iter().position(|owner| owner == key)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[account]
+pub struct MultisigProposal {
+    pub bump: u8,
+    pub multisig: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccount>,
+    pub data: Vec<u8>,
+    // one entry per multisig owner, in the same order
+    pub approvals: Vec<bool>,
+    pub owner_set_seqno: u32,
+    pub executed: bool,
+}
+
+impl MultisigProposal {
+    pub fn space(accounts_len: usize, data_len: usize, owners_len: usize) -> usize {
+        8 + 1
+            + 32
+            + 8
+            + 32
+            + 32
+            + 4
+            + accounts_len * ProposalAccount::INIT_SPACE
+            + 4
+            + data_len
+            + 4
+            + owners_len
+            + 4
+            + 1
+    }
+
+    pub fn approval_count(&self) -> usize {
+        self.approvals.iter().filter(|approved| **approved).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal(accounts_len: usize, data_len: usize, approvals: Vec<bool>) -> MultisigProposal {
+        MultisigProposal {
+            bump: 0,
+            multisig: Pubkey::default(),
+            id: 0,
+            proposer: Pubkey::default(),
+            program_id: Pubkey::default(),
+            accounts: vec![
+                ProposalAccount {
+                    pubkey: Pubkey::default(),
+                    is_signer: false,
+                    is_writable: true,
+                };
+                accounts_len
+            ],
+            data: vec![0; data_len],
+            approvals,
+            owner_set_seqno: 0,
+            executed: false,
+        }
+    }
+
+    #[test]
+    fn approvals_are_counted_per_owner() {
+        assert_eq!(proposal(0, 0, vec![false; 3]).approval_count(), 0);
+        assert_eq!(proposal(0, 0, vec![true, false, true]).approval_count(), 2);
+    }
+
+    #[test]
+    fn owners_are_found_by_key() {
+        let owners = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let multisig = Multisig {
+            bump: 0,
+            signer_bump: 0,
+            create_key: Pubkey::default(),
+            owners: owners.clone(),
+            threshold: 1,
+            proposal_count: 0,
+            owner_set_seqno: 0,
+        };
+        assert_eq!(multisig.owner_index(&owners[1]), Some(1));
+        assert_eq!(multisig.owner_index(&Pubkey::new_unique()), None);
+    }
+
+    #[test]
+    fn proposal_space_fits_the_serialized_proposal() {
+        let proposal = proposal(3, 40, vec![false; 5]);
+        let mut data = Vec::new();
+        proposal.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), MultisigProposal::space(3, 40, 5));
+    }
+}
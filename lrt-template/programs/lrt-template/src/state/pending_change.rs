@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InitSpace;
+
+use super::DepositCaps;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub enum ConfigChange {
+    DelegateAuthority {
+        new_authority: Pubkey,
+    },
+    Admin {
+        new_admin: Pubkey,
+    },
+    Guardian {
+        new_guardian: Pubkey,
+    },
+    TimelockDelay {
+        delay: i64,
+    },
+    DepositCaps {
+        caps: DepositCaps,
+    },
+    Fees {
+        deposit_fee_bps: u16,
+        referral_fee_share_bps: u16,
+    },
+    MinHoldingPeriod {
+        slots: u64,
+    },
+    RewardVestingPeriod {
+        period: i64,
+    },
+    RateLimits {
+        max_rate_increase_per_update_bps: u16,
+        max_rate_increase_per_epoch_bps: u16,
+    },
+    Allowlist {
+        enabled: bool,
+        merkle_root: [u8; 32],
+    },
+    PointsRate {
+        points_rate: u64,
+    },
+    AvsPointsMultiplier {
+        avs: Pubkey,
+        multiplier_bps: u16,
+    },
+    AddAvs {
+        avs: Pubkey,
+        avs_program: Pubkey,
+    },
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingChange {
+    pub bump: u8,
+    pub pool: Pubkey,
+    pub id: u64,
+    pub change: ConfigChange,
+    // unix timestamp from which the change can be executed
+    pub eta: i64,
+}
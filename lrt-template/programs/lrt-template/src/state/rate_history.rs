@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InitSpace;
+
+use super::exchange_rate_of;
+
+pub const RATE_HISTORY_LEN: usize = 64;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct RateSnapshot {
+    pub slot: u64,
+    pub timestamp: i64,
+    pub epoch: u64,
+    pub total_assets: u64,
+    pub supply: u64,
+}
+
+impl RateSnapshot {
+    pub fn exchange_rate(&self) -> u64 {
+        exchange_rate_of(self.total_assets, self.supply)
+    }
+}
+
+// ring buffer of exchange rate snapshots, at most one per epoch
+#[account]
+#[derive(InitSpace)]
+pub struct RateHistory {
+    pub bump: u8,
+    pub pool: Pubkey,
+    // index the next snapshot is written to
+    pub head: u16,
+    pub len: u16,
+    pub snapshots: [RateSnapshot; RATE_HISTORY_LEN],
+}
+
+impl RateHistory {
+    // snapshots from oldest to newest
+    pub fn iter(&self) -> impl Iterator<Item = &RateSnapshot> {
+        let start = self.head as usize + RATE_HISTORY_LEN - self.len as usize;
+        (0..self.len as usize).map(move |i| &self.snapshots[(start + i) % RATE_HISTORY_LEN])
+    }
+
+    pub fn latest(&self) -> Option<&RateSnapshot> {
+        self.iter().last()
+    }
+
+    pub fn push(&mut self, snapshot: RateSnapshot) {
+        self.snapshots[self.head as usize] = snapshot;
+        self.head = ((self.head as usize + 1) % RATE_HISTORY_LEN) as u16;
+        if (self.len as usize) < RATE_HISTORY_LEN {
+            self.len += 1;
+        }
+    }
+
+    // time-weighted average exchange rate from since to now, each snapshot's rate holds
+    // until the next one; the window is clipped to the oldest snapshot
+    pub fn twap(&self, since: i64, now: i64) -> Option<u64> {
+        let snapshots: Vec<&RateSnapshot> = self.iter().collect();
+        let latest = snapshots.last()?;
+
+        let mut weighted_sum: u128 = 0;
+        let mut total_time: u128 = 0;
+        for (i, snapshot) in snapshots.iter().enumerate() {
+            let end = snapshots.get(i + 1).map_or(now, |next| next.timestamp);
+            let start = snapshot.timestamp.max(since);
+            if end > start {
+                let elapsed = (end - start) as u128;
+                weighted_sum += snapshot.exchange_rate() as u128 * elapsed;
+                total_time += elapsed;
+            }
+        }
+
+        if total_time == 0 {
+            return Some(latest.exchange_rate());
+        }
+        Some((weighted_sum / total_time) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::EXCHANGE_RATE_PRECISION;
+
+    fn history() -> RateHistory {
+        RateHistory::deserialize(&mut &[0u8; RateHistory::INIT_SPACE][..]).unwrap()
+    }
+
+    // exchange rate of rate_bps / 10_000
+    fn snapshot(timestamp: i64, rate_bps: u64) -> RateSnapshot {
+        RateSnapshot {
+            slot: timestamp as u64,
+            timestamp,
+            epoch: timestamp as u64,
+            total_assets: rate_bps,
+            supply: 10_000,
+        }
+    }
+
+    #[test]
+    fn push_wraps_around() {
+        let mut history = history();
+        for i in 0..RATE_HISTORY_LEN as i64 + 3 {
+            history.push(snapshot(i, 10_000));
+        }
+        assert_eq!(history.len as usize, RATE_HISTORY_LEN);
+        assert_eq!(history.head, 3);
+        let timestamps: Vec<i64> = history.iter().map(|snapshot| snapshot.timestamp).collect();
+        assert_eq!(timestamps.len(), RATE_HISTORY_LEN);
+        assert_eq!(timestamps[0], 3);
+        assert!(timestamps.windows(2).all(|pair| pair[1] == pair[0] + 1));
+        assert_eq!(
+            history.latest().unwrap().timestamp,
+            RATE_HISTORY_LEN as i64 + 2
+        );
+    }
+
+    #[test]
+    fn twap_weights_rates_by_time_across_the_wraparound() {
+        let mut history = history();
+        for i in 0..RATE_HISTORY_LEN as i64 {
+            history.push(snapshot(i * 100, 10_000));
+        }
+        // overwrites the two oldest snapshots
+        history.push(snapshot(RATE_HISTORY_LEN as i64 * 100, 11_000));
+        history.push(snapshot(RATE_HISTORY_LEN as i64 * 100 + 100, 12_000));
+
+        let now = RATE_HISTORY_LEN as i64 * 100 + 200;
+        // 100s at 1.0, 100s at 1.1 and 100s at 1.2
+        assert_eq!(
+            history.twap(now - 300, now),
+            Some(EXCHANGE_RATE_PRECISION / 10 * 11)
+        );
+        // the window is clipped to the oldest snapshot still kept
+        let oldest = history.iter().next().unwrap().timestamp;
+        assert_eq!(oldest, 200);
+        assert_eq!(history.twap(0, now), history.twap(oldest, now));
+    }
+
+    #[test]
+    fn twap_inside_one_snapshot_is_its_rate() {
+        let mut history = history();
+        history.push(snapshot(0, 10_000));
+        history.push(snapshot(1_000, 15_000));
+        assert_eq!(
+            history.twap(1_010, 1_020),
+            Some(EXCHANGE_RATE_PRECISION / 2 * 3)
+        );
+        // a window ending at the latest snapshot falls back to its rate
+        assert_eq!(
+            history.twap(1_000, 1_000),
+            Some(EXCHANGE_RATE_PRECISION / 2 * 3)
+        );
+    }
+
+    #[test]
+    fn twap_of_an_empty_history_is_none() {
+        assert_eq!(history().twap(0, 100), None);
+    }
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InitSpace;
+
+// a referrer's share of the deposit fees, held in the pool input token vault until claimed
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerRewards {
+    pub bump: u8,
+    pub pool: Pubkey,
+    pub referrer: Pubkey,
+    pub unclaimed_amount: u64,
+    pub total_earned: u64,
+    pub total_claimed: u64,
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InitSpace;
+
+// rewards harvested in one reward token mint, the tokens are held in the pool's
+// associated token account of that mint
+#[account]
+#[derive(InitSpace)]
+pub struct RewardVault {
+    pub bump: u8,
+    pub pool: Pubkey,
+    pub reward_token_mint: Pubkey,
+    pub total_harvested: u64,
+}
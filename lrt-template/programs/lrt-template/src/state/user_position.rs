@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InitSpace;
+
+use crate::errors::LRTPoolError;
+
+// input amounts are counted at the pool, i.e. after transfer fees on deposit
+// and before transfer fees on withdrawal
+#[account]
+#[derive(InitSpace)]
+pub struct UserPosition {
+    pub bump: u8,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    // input tokens deposited minus input tokens withdrawn, counted against the user deposit cap
+    pub deposited_amount: u64,
+    // cost basis for realized and unrealized pnl
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub total_minted: u64,
+    pub total_burned: u64,
+    pub first_deposit_slot: u64,
+    pub last_deposit_slot: u64,
+    // set by the first deposit that names a referrer, later deposits must name the same one
+    pub referrer: Option<Pubkey>,
+}
+
+impl UserPosition {
+    // fills in the keys of a position created by init_if_needed
+    pub fn init_if_needed(&mut self, bump: u8, pool: Pubkey, user: Pubkey) {
+        if self.pool == Pubkey::default() {
+            self.bump = bump;
+            self.pool = pool;
+            self.user = user;
+        }
+    }
+
+    pub fn record_deposit(
+        &mut self,
+        input_amount: u64,
+        minted_amount: u64,
+        slot: u64,
+    ) -> Result<()> {
+        self.deposited_amount = self
+            .deposited_amount
+            .checked_add(input_amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        self.total_deposited = self
+            .total_deposited
+            .checked_add(input_amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        self.total_minted = self
+            .total_minted
+            .checked_add(minted_amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        if self.first_deposit_slot == 0 {
+            self.first_deposit_slot = slot;
+        }
+        self.last_deposit_slot = slot;
+        Ok(())
+    }
+
+    // once recorded, the referrer earns the fee share of every later deposit
+    pub fn check_referrer(&self, referrer: Option<Pubkey>) -> Result<()> {
+        if self.referrer.is_some() && referrer != self.referrer {
+            return Err(LRTPoolError::ReferrerMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn record_withdrawal(&mut self, input_amount: u64, burned_amount: u64) -> Result<()> {
+        self.deposited_amount = self.deposited_amount.saturating_sub(input_amount);
+        self.total_withdrawn = self
+            .total_withdrawn
+            .checked_add(input_amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        self.total_burned = self
+            .total_burned
+            .checked_add(burned_amount)
+            .ok_or(LRTPoolError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(referrer: Option<Pubkey>) -> UserPosition {
+        UserPosition {
+            bump: 0,
+            pool: Pubkey::default(),
+            user: Pubkey::default(),
+            deposited_amount: 0,
+            total_deposited: 0,
+            total_withdrawn: 0,
+            total_minted: 0,
+            total_burned: 0,
+            first_deposit_slot: 0,
+            last_deposit_slot: 0,
+            referrer,
+        }
+    }
+
+    #[test]
+    fn deposits_credit_the_recorded_referrer() {
+        let referrer = Pubkey::new_unique();
+        assert!(position(None).check_referrer(None).is_ok());
+        assert!(position(None).check_referrer(Some(referrer)).is_ok());
+
+        let expected = Err(LRTPoolError::ReferrerMismatch.into());
+        let position = position(Some(referrer));
+        assert!(position.check_referrer(Some(referrer)).is_ok());
+        assert_eq!(position.check_referrer(None), expected);
+        assert_eq!(
+            position.check_referrer(Some(Pubkey::new_unique())),
+            expected
+        );
+    }
+}
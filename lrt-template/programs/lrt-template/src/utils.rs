@@ -1,6 +1,121 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{
+        allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+    },
+};
+
 pub fn sighash(namespace: &str, name: &str) -> [u8; 8] {
     let preimage = format!("{}:{}", namespace, name);
     let mut sighash = [0u8; 8];
     sighash.copy_from_slice(&solana_program::hash::hash(preimage.as_bytes()).to_bytes()[..8]);
     sighash
 }
+
+// creates a PDA owned by owner like anchor's init does, which also works when lamports were
+// sent to the address beforehand and create_account would fail with AccountAlreadyInUse
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let current_lamports = account.lamports();
+    if current_lamports == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            lamports,
+            space,
+            owner,
+        );
+    }
+
+    let shortfall = lamports.saturating_sub(current_lamports);
+    if shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer_seeds,
+        ),
+        space,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: account.clone(),
+            },
+            signer_seeds,
+        ),
+        owner,
+    )
+}
+
+// pairs are hashed in sorted order, so the proof does not need to encode left or right
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            solana_program::hash::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            solana_program::hash::hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::hash::hashv;
+
+    fn leaf(i: u8) -> [u8; 32] {
+        hashv(&[&[i]]).to_bytes()
+    }
+
+    fn parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[&a, &b]).to_bytes()
+        } else {
+            hashv(&[&b, &a]).to_bytes()
+        }
+    }
+
+    #[test]
+    fn merkle_proofs_are_verified_against_the_root() {
+        let (l0, l1, l2) = (leaf(0), leaf(1), leaf(2));
+        let root = parent(parent(l0, l1), l2);
+
+        assert!(verify_merkle_proof(&[l1, l2], root, l0));
+        assert!(verify_merkle_proof(&[l0, l2], root, l1));
+        assert!(verify_merkle_proof(&[parent(l0, l1)], root, l2));
+
+        assert!(!verify_merkle_proof(&[l1, l2], root, leaf(3)));
+        assert!(!verify_merkle_proof(&[l2, l1], root, l0));
+        assert!(!verify_merkle_proof(&[l1], root, l0));
+        assert!(verify_merkle_proof(&[], l0, l0));
+    }
+}
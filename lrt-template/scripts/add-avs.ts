@@ -0,0 +1,121 @@
+import { Program } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import LrtTemplate from "../target/idl/lrt_template.json";
+import {
+  clusterApiUrl,
+  Connection,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  sendAndConfirmTransaction,
+  SystemProgram,
+} from "@solana/web3.js";
+import {
+  findAvsAllowlist,
+  findOutputTokenMint,
+  log,
+  newTransactionWithComputeUnitPriceAndLimit,
+} from "./helpers";
+import {
+  ENDO_AVS_DEVNET,
+  ENDO_AVS_PROGRAM_ID_DEVNET,
+  LRT_TEMPLATE_PROGRAM_ID_DEVNET,
+  OUTPUT_TOKEN_SYMBOL,
+  SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+} from "./constants";
+
+// admin keypair
+const KEYPAIR = Keypair.fromSecretKey(
+  new Uint8Array([
+    156, 213, 112, 118, 70, 144, 0, 183, 8, 253, 100, 218, 180, 250, 254, 252,
+    85, 48, 245, 134, 56, 252, 57, 206, 164, 47, 215, 247, 219, 8, 190, 122,
+    206, 132, 140, 223, 170, 178, 96, 245, 208, 40, 160, 135, 72, 255, 132, 235,
+    4, 15, 35, 86, 66, 167, 108, 172, 66, 84, 186, 235, 73, 53, 211, 225,
+  ])
+);
+
+// created by initialize, derived from the input token mint, the admin and the symbol
+const OUTPUT_TOKEN_MINT = findOutputTokenMint(
+  SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+  KEYPAIR.publicKey,
+  OUTPUT_TOKEN_SYMBOL
+);
+
+async function main() {
+  const connection = new Connection(clusterApiUrl("devnet"));
+  console.log(`signer wallet public key is: ${KEYPAIR.publicKey}`);
+  console.log(
+    `signer wallet balance is: ${
+      (await connection.getBalance(KEYPAIR.publicKey)) / LAMPORTS_PER_SOL
+    } SOL`
+  );
+
+  const program = new Program(
+    LrtTemplate as anchor.Idl,
+    LRT_TEMPLATE_PROGRAM_ID_DEVNET,
+    { connection }
+  );
+
+  const [pool] = PublicKey.findProgramAddressSync(
+    [Buffer.from("lrt_pool"), OUTPUT_TOKEN_MINT.toBuffer()],
+    program.programId
+  );
+  const poolAccountInfo = await program.account.lrtPool.fetch(pool);
+
+  const [pendingChange] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("pending_change"),
+      pool.toBuffer(),
+      (poolAccountInfo.changeNonce as anchor.BN).toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  );
+  const avsAllowlist = findAvsAllowlist(
+    pool,
+    ENDO_AVS_DEVNET,
+    ENDO_AVS_PROGRAM_ID_DEVNET
+  );
+  console.log("avs_allowlist(init): ", avsAllowlist.toBase58());
+
+  let tx = newTransactionWithComputeUnitPriceAndLimit();
+
+  const queueChangeInst = await program.methods
+    .queueChange({
+      addAvs: {
+        avs: ENDO_AVS_DEVNET,
+        avsProgram: ENDO_AVS_PROGRAM_ID_DEVNET,
+      },
+    })
+    .accounts({
+      admin: KEYPAIR.publicKey,
+      pool,
+      pendingChange,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+  tx.add(queueChangeInst);
+
+  // the pool is initialized with a zero timelock delay in the devnet tests,
+  // otherwise execute the change once the delay has elapsed
+  const executeChangeInst = await program.methods
+    .executeChange()
+    .accounts({
+      admin: KEYPAIR.publicKey,
+      pool,
+      outputTokenMint: OUTPUT_TOKEN_MINT,
+      pendingChange,
+      avsPoints: null,
+      avsAllowlist,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+  tx.add(executeChangeInst);
+
+  try {
+    await sendAndConfirmTransaction(connection, tx, [KEYPAIR]).then(log);
+  } catch (error) {
+    console.error(error);
+  }
+}
+
+main().then(() => process.exit());
@@ -32,6 +32,11 @@ export const ENDO_AVS_TOKEN_MINT_DEVNET = new PublicKey(
   "5RA2wjzePPnk8z9Zy3whTDk4jTbMXgXqWxvCoeh8Fgck"
 );
 
+// output token (LRT) metadata, the symbol is also part of the output token mint address
+export const OUTPUT_TOKEN_NAME = "LRT Template";
+export const OUTPUT_TOKEN_SYMBOL = "LRT";
+export const OUTPUT_TOKEN_URI = "";
+
 // mainnet account
 export const SOLAYER_RESTAKE_PROGRAM_ID_MAINNET = new PublicKey(
   "sSo1iU21jBrU9VaJ8PJib1MtorefUV4fzC9GURa2KNn"
@@ -12,13 +12,18 @@ import {
 } from "@solana/web3.js";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotentInstruction,
   createMintToInstruction,
   getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   airdropSol,
   createTokenAccount,
+  findHolderCheckpoint,
+  findOutputTokenMint,
+  findUserPosition,
   loadKeypairFromFile,
   log,
   newTransactionWithComputeUnitPriceAndLimit,
@@ -30,6 +35,7 @@ import {
   SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
   SOLAYER_RESTAKE_PROGRAM_ID_DEVNET,
   SOLAYER_RESTAKE_POOL_DEVNET,
+  OUTPUT_TOKEN_SYMBOL,
 } from "./constants";
 import { depositSol } from "@solana/spl-stake-pool";
 import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
@@ -46,9 +52,11 @@ const KEYPAIR = Keypair.fromSecretKey(
 
 const USER_KEYPAIR = loadKeypairFromFile("./keys/user.json");
 
-// use the same one as initialize
-const OUTPUT_TOKEN_MINT_KEYPAIR = loadKeypairFromFile(
-  "./keys/output_token_mint.json"
+// created by initialize, derived from the input token mint, the admin and the symbol
+const OUTPUT_TOKEN_MINT = findOutputTokenMint(
+  SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+  KEYPAIR.publicKey,
+  OUTPUT_TOKEN_SYMBOL
 );
 
 const DEPOSIT_AMOUNT = 10;
@@ -72,7 +80,7 @@ async function main() {
   );
 
   const [pool, bump] = PublicKey.findProgramAddressSync(
-    [Buffer.from("lrt_pool"), OUTPUT_TOKEN_MINT_KEYPAIR.publicKey.toBuffer()],
+    [Buffer.from("lrt_pool"), OUTPUT_TOKEN_MINT.toBuffer()],
     program.programId
   );
 
@@ -89,9 +97,10 @@ async function main() {
   );
 
   const signerOutputTokenVault = getAssociatedTokenAddressSync(
-    OUTPUT_TOKEN_MINT_KEYPAIR.publicKey,
+    OUTPUT_TOKEN_MINT,
     USER_KEYPAIR.publicKey,
-    true
+    true,
+    TOKEN_2022_PROGRAM_ID
   );
 
   console.log("input_token_mint: ", SOLAYER_SOL_MINT_PUB_KEY_DEVNET.toBase58());
@@ -99,10 +108,10 @@ async function main() {
   console.log("pool_input_token_vault: ", poolInputTokenVault.toBase58());
   console.log(
     "output_token_mint: ",
-    OUTPUT_TOKEN_MINT_KEYPAIR.publicKey.toBase58()
+    OUTPUT_TOKEN_MINT.toBase58()
   );
   console.log(
-    "signer_output_token_vault (init_if_needed): ",
+    "signer_output_token_vault: ",
     signerOutputTokenVault.toBase58()
   );
   console.log("pool and bump: ", pool.toBase58(), bump);
@@ -175,18 +184,35 @@ async function main() {
 
   tx.add(restakeInst);
 
+  // the pool mints to any output token account of the recipient, use its ATA here
+  tx.add(
+    createAssociatedTokenAccountIdempotentInstruction(
+      USER_KEYPAIR.publicKey,
+      signerOutputTokenVault,
+      USER_KEYPAIR.publicKey,
+      OUTPUT_TOKEN_MINT,
+      TOKEN_2022_PROGRAM_ID
+    )
+  );
+
   const depositInst = await program.methods
     .deposit(new anchor.BN(DEPOSIT_AMOUNT * LAMPORTS_PER_SOL))
     .accounts({
       signer: USER_KEYPAIR.publicKey,
+      recipient: USER_KEYPAIR.publicKey,
       inputTokenMint: SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
       signerInputTokenVault,
       poolInputTokenVault,
-      outputTokenMint: OUTPUT_TOKEN_MINT_KEYPAIR.publicKey,
-      signerOutputTokenVault,
+      outputTokenMint: OUTPUT_TOKEN_MINT,
+      recipientOutputTokenVault: signerOutputTokenVault,
       pool,
-      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-      tokenProgram: TOKEN_PROGRAM_ID,
+      signerAllowlistEntry: null,
+      recipientAllowlistEntry: null,
+      userPosition: findUserPosition(pool, USER_KEYPAIR.publicKey),
+      referrerRewards: null,
+      recipientCheckpoint: findHolderCheckpoint(pool, signerOutputTokenVault),
+      inputTokenProgram: TOKEN_PROGRAM_ID,
+      outputTokenProgram: TOKEN_2022_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
     })
     .instruction();
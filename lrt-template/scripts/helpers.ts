@@ -11,6 +11,7 @@ import {
   Transaction,
 } from "@solana/web3.js";
 import * as path from "path";
+import { LRT_TEMPLATE_PROGRAM_ID_DEVNET } from "./constants";
 import {
   AuthorityType,
   createAssociatedTokenAccount,
@@ -186,3 +187,90 @@ export async function createTokenAccount(
     owner
   );
 }
+
+// the output token mint is created by initialize at this address
+export function findOutputTokenMint(
+  inputTokenMint: PublicKey,
+  admin: PublicKey,
+  symbol: string,
+  programId = LRT_TEMPLATE_PROGRAM_ID_DEVNET
+): PublicKey {
+  const [outputTokenMint] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("output_token_mint"),
+      inputTokenMint.toBuffer(),
+      admin.toBuffer(),
+      Buffer.from(symbol),
+    ],
+    programId
+  );
+  return outputTokenMint;
+}
+
+// tracks the user's net deposits against the pool's user deposit cap
+export function findUserPosition(
+  pool: PublicKey,
+  user: PublicKey,
+  programId = LRT_TEMPLATE_PROGRAM_ID_DEVNET
+): PublicKey {
+  const [userPosition] = PublicKey.findProgramAddressSync(
+    [Buffer.from("user_position"), pool.toBuffer(), user.toBuffer()],
+    programId
+  );
+  return userPosition;
+}
+
+// the output token transfer hook keeps reward checkpoints per output token account
+export function findHolderCheckpoint(
+  pool: PublicKey,
+  tokenAccount: PublicKey,
+  programId = LRT_TEMPLATE_PROGRAM_ID_DEVNET
+): PublicKey {
+  const [holderCheckpoint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("holder_checkpoint"), pool.toBuffer(), tokenAccount.toBuffer()],
+    programId
+  );
+  return holderCheckpoint;
+}
+
+export function findAvsPoints(
+  pool: PublicKey,
+  avs: PublicKey,
+  programId = LRT_TEMPLATE_PROGRAM_ID_DEVNET
+): PublicKey {
+  const [avsPoints] = PublicKey.findProgramAddressSync(
+    [Buffer.from("avs_points"), pool.toBuffer(), avs.toBuffer()],
+    programId
+  );
+  return avsPoints;
+}
+
+// created by a timelocked AddAvs change, the pool only signs CPIs into allowlisted avs
+export function findAvsAllowlist(
+  pool: PublicKey,
+  avs: PublicKey,
+  avsProgram: PublicKey,
+  programId = LRT_TEMPLATE_PROGRAM_ID_DEVNET
+): PublicKey {
+  const [avsAllowlist] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("avs_allowlist"),
+      pool.toBuffer(),
+      avs.toBuffer(),
+      avsProgram.toBuffer(),
+    ],
+    programId
+  );
+  return avsAllowlist;
+}
+
+export function findExtraAccountMetaList(
+  outputTokenMint: PublicKey,
+  programId = LRT_TEMPLATE_PROGRAM_ID_DEVNET
+): PublicKey {
+  const [extraAccountMetaList] = PublicKey.findProgramAddressSync(
+    [Buffer.from("extra-account-metas"), outputTokenMint.toBuffer()],
+    programId
+  );
+  return extraAccountMetaList;
+}
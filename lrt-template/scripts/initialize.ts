@@ -13,18 +13,22 @@ import {
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
-  createMintInstructions,
+  findExtraAccountMetaList,
+  findOutputTokenMint,
   loadKeypairFromFile,
   log,
-  mintSetAuthorityInstruction,
   newTransactionWithComputeUnitPriceAndLimit,
 } from "./helpers";
 import {
   LRT_TEMPLATE_PROGRAM_ID_DEVNET,
   SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+  OUTPUT_TOKEN_NAME,
+  OUTPUT_TOKEN_SYMBOL,
+  OUTPUT_TOKEN_URI,
 } from "./constants";
 
 // signer keypair
@@ -42,9 +46,29 @@ const DELEGATE_AUTHORITY = loadKeypairFromFile(
   "./keys/delegate_authority.json"
 );
 
-// you can generate a new one for your use
-const OUTPUT_TOKEN_MINT_KEYPAIR = loadKeypairFromFile(
-  "./keys/output_token_mint.json"
+// seconds queued configuration changes wait before they can be executed
+// keep it at zero for the devnet tests and use a real delay in production
+const TIMELOCK_DELAY = 0;
+
+// input token mint risks the admin accepts, initialize rejects any other one
+// freeze authority = 1, permanent delegate = 2, mint close authority = 16
+const ACCEPTED_INPUT_MINT_RISKS = 0;
+
+// guarded launch, in input token units with 0 meaning unlimited
+// the tvl cap ramps from rampInitialCap to depositCap between rampStart and rampEnd
+const DEPOSIT_CAPS = {
+  depositCap: new anchor.BN(0),
+  userDepositCap: new anchor.BN(0),
+  rampInitialCap: new anchor.BN(0),
+  rampStart: new anchor.BN(0),
+  rampEnd: new anchor.BN(0),
+};
+
+// created by initialize, derived from the input token mint, the admin and the symbol
+const OUTPUT_TOKEN_MINT = findOutputTokenMint(
+  SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+  KEYPAIR.publicKey,
+  OUTPUT_TOKEN_SYMBOL
 );
 
 async function main() {
@@ -63,7 +87,7 @@ async function main() {
   );
 
   const [pool, bump] = PublicKey.findProgramAddressSync(
-    [Buffer.from("lrt_pool"), OUTPUT_TOKEN_MINT_KEYPAIR.publicKey.toBuffer()],
+    [Buffer.from("lrt_pool"), OUTPUT_TOKEN_MINT.toBuffer()],
     program.programId
   );
 
@@ -75,48 +99,40 @@ async function main() {
 
   console.log("signer: ", KEYPAIR.publicKey.toBase58());
   console.log("delegate_authority: ", DELEGATE_AUTHORITY.publicKey.toBase58());
+  console.log("admin: ", KEYPAIR.publicKey.toBase58());
+  console.log("guardian: ", KEYPAIR.publicKey.toBase58());
   console.log("input_token_mint: ", SOLAYER_SOL_MINT_PUB_KEY_DEVNET.toBase58());
   console.log(
     "poolInputTokenVault(init_if_needed): ",
     poolInputTokenVault.toBase58()
   );
-  console.log(
-    "output_token_mint: ",
-    OUTPUT_TOKEN_MINT_KEYPAIR.publicKey.toBase58()
-  );
+  console.log("output_token_mint(init): ", OUTPUT_TOKEN_MINT.toBase58());
   console.log("pool(init), bump: ", pool.toBase58(), bump);
 
   let tx = newTransactionWithComputeUnitPriceAndLimit();
 
-  const outputTokenMintInst = await createMintInstructions(
-    connection,
-    KEYPAIR,
-    KEYPAIR.publicKey,
-    KEYPAIR.publicKey,
-    9,
-    OUTPUT_TOKEN_MINT_KEYPAIR
-  );
-
-  tx.add(outputTokenMintInst);
-
-  const setAuthorityInstructions = mintSetAuthorityInstruction(
-    OUTPUT_TOKEN_MINT_KEYPAIR.publicKey,
-    KEYPAIR.publicKey,
-    pool
-  );
-  tx.add(setAuthorityInstructions);
-
   const initializeLRTPoolInst = await program.methods
-    .initialize()
+    .initialize(
+      new anchor.BN(TIMELOCK_DELAY),
+      OUTPUT_TOKEN_NAME,
+      OUTPUT_TOKEN_SYMBOL,
+      OUTPUT_TOKEN_URI,
+      ACCEPTED_INPUT_MINT_RISKS,
+      DEPOSIT_CAPS
+    )
     .accounts({
       signer: KEYPAIR.publicKey,
       delegateAuthority: DELEGATE_AUTHORITY.publicKey,
+      admin: KEYPAIR.publicKey,
+      guardian: KEYPAIR.publicKey,
       inputTokenMint: SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
       poolInputTokenVault,
-      outputTokenMint: OUTPUT_TOKEN_MINT_KEYPAIR.publicKey,
+      outputTokenMint: OUTPUT_TOKEN_MINT,
       pool,
+      extraAccountMetaList: findExtraAccountMetaList(OUTPUT_TOKEN_MINT),
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-      tokenProgram: TOKEN_PROGRAM_ID,
+      inputTokenProgram: TOKEN_PROGRAM_ID,
+      outputTokenProgram: TOKEN_2022_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
     })
     .instruction();
@@ -126,7 +142,6 @@ async function main() {
     await sendAndConfirmTransaction(connection, tx, [
       KEYPAIR,
       DELEGATE_AUTHORITY,
-      OUTPUT_TOKEN_MINT_KEYPAIR,
     ]).then(log);
   } catch (error) {
     console.error(error);
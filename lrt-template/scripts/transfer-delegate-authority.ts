@@ -8,16 +8,22 @@ import {
   LAMPORTS_PER_SOL,
   PublicKey,
   sendAndConfirmTransaction,
+  SystemProgram,
 } from "@solana/web3.js";
 import {
+  findOutputTokenMint,
   loadKeypairFromFile,
   log,
   newTransactionWithComputeUnitPriceAndLimit,
 } from "./helpers";
 import { assert } from "chai";
-import { LRT_TEMPLATE_PROGRAM_ID_DEVNET } from "./constants";
+import {
+  LRT_TEMPLATE_PROGRAM_ID_DEVNET,
+  OUTPUT_TOKEN_SYMBOL,
+  SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+} from "./constants";
 
-// signer keypair
+// admin keypair
 const KEYPAIR = Keypair.fromSecretKey(
   new Uint8Array([
     156, 213, 112, 118, 70, 144, 0, 183, 8, 253, 100, 218, 180, 250, 254, 252,
@@ -36,8 +42,12 @@ const NEW_DELEGATE_AUTHORITY = loadKeypairFromFile(
   "./keys/new_delegate_authority.json"
 );
 
-// you can generate a new one for your use
-const OUTPUT_TOKEN_MINT_KEYPAIR = loadKeypairFromFile("./keys/output_token_mint.json");
+// created by initialize, derived from the input token mint, the admin and the symbol
+const OUTPUT_TOKEN_MINT = findOutputTokenMint(
+  SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+  KEYPAIR.publicKey,
+  OUTPUT_TOKEN_SYMBOL
+);
 
 async function main() {
   const connection = new Connection(clusterApiUrl("devnet"));
@@ -57,7 +67,7 @@ async function main() {
   const [pool, _] = PublicKey.findProgramAddressSync(
     [
       Buffer.from("lrt_pool"),
-      OUTPUT_TOKEN_MINT_KEYPAIR.publicKey.toBuffer(),
+      OUTPUT_TOKEN_MINT.toBuffer(),
     ],
     program.programId
   );
@@ -70,17 +80,61 @@ async function main() {
     (poolAccountInfoBefore.delegateAuthority as PublicKey).toBase58()
   );
 
-  const transferDelegateAuthorityInst = await program.methods
-    .transferDelegateAuthority()
+  const [pendingChange] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("pending_change"),
+      pool.toBuffer(),
+      (poolAccountInfoBefore.changeNonce as anchor.BN).toArrayLike(
+        Buffer,
+        "le",
+        8
+      ),
+    ],
+    program.programId
+  );
+
+  const queueChangeInst = await program.methods
+    .queueChange({
+      delegateAuthority: { newAuthority: NEW_DELEGATE_AUTHORITY.publicKey },
+    })
     .accounts({
-      authority: DELEGATE_AUTHORITY.publicKey,
+      admin: KEYPAIR.publicKey,
       pool,
+      pendingChange: pendingChange,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+  tx1.add(queueChangeInst);
+
+  // the pool is initialized with a zero timelock delay in the devnet tests,
+  // otherwise execute the change once the delay has elapsed
+  const executeChangeInst = await program.methods
+    .executeChange()
+    .accounts({
+      admin: KEYPAIR.publicKey,
+      pool,
+      outputTokenMint: OUTPUT_TOKEN_MINT,
+      pendingChange: pendingChange,
+      avsPoints: null,
+      avsAllowlist: null,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+  tx1.add(executeChangeInst);
+
+  const acceptAuthorityInst = await program.methods
+    .acceptAuthority()
+    .accounts({
       newAuthority: NEW_DELEGATE_AUTHORITY.publicKey,
+      pool,
     })
     .instruction();
-  tx1.add(transferDelegateAuthorityInst);
+  tx1.add(acceptAuthorityInst);
 
-  await sendAndConfirmTransaction(connection, tx1, [DELEGATE_AUTHORITY]);
+  await sendAndConfirmTransaction(connection, tx1, [
+    KEYPAIR,
+    NEW_DELEGATE_AUTHORITY,
+  ]);
 
   await new Promise((f) => setTimeout(f, 3000));
 
@@ -91,18 +145,58 @@ async function main() {
   );
 
   let tx2 = newTransactionWithComputeUnitPriceAndLimit();
-  const transferDelegateAuthorityBackInst = await program.methods
-    .transferDelegateAuthority()
+  const [pendingChangeBack] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("pending_change"),
+      pool.toBuffer(),
+      (poolAccountInfoAfter.changeNonce as anchor.BN).toArrayLike(
+        Buffer,
+        "le",
+        8
+      ),
+    ],
+    program.programId
+  );
+
+  const queueChangeBackInst = await program.methods
+    .queueChange({
+      delegateAuthority: { newAuthority: DELEGATE_AUTHORITY.publicKey },
+    })
+    .accounts({
+      admin: KEYPAIR.publicKey,
+      pool,
+      pendingChange: pendingChangeBack,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+  tx2.add(queueChangeBackInst);
+
+  const executeChangeBackInst = await program.methods
+    .executeChange()
     .accounts({
-      authority: NEW_DELEGATE_AUTHORITY.publicKey,
+      admin: KEYPAIR.publicKey,
       pool,
+      outputTokenMint: OUTPUT_TOKEN_MINT,
+      pendingChange: pendingChangeBack,
+      avsPoints: null,
+      avsAllowlist: null,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+  tx2.add(executeChangeBackInst);
+
+  const acceptAuthorityBackInst = await program.methods
+    .acceptAuthority()
+    .accounts({
       newAuthority: DELEGATE_AUTHORITY.publicKey,
+      pool,
     })
     .instruction();
-  tx2.add(transferDelegateAuthorityBackInst);
+  tx2.add(acceptAuthorityBackInst);
 
   await sendAndConfirmTransaction(connection, tx2, [
-    NEW_DELEGATE_AUTHORITY,
+    KEYPAIR,
+    DELEGATE_AUTHORITY,
   ]).then((signature: string) => {
     console.log("Transfer Delegate Authority Tx Success.");
     log(signature);
@@ -115,6 +209,7 @@ async function main() {
     DELEGATE_AUTHORITY.publicKey.toBase58(),
     (poolAccountInfoRevert.delegateAuthority as PublicKey).toBase58()
   );
+  assert.isNull(poolAccountInfoRevert.pendingDelegateAuthority);
 }
 
 main().then(() => process.exit());
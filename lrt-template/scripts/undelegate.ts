@@ -11,6 +11,9 @@ import {
   SystemProgram,
 } from "@solana/web3.js";
 import {
+  findAvsAllowlist,
+  findAvsPoints,
+  findOutputTokenMint,
   loadKeypairFromFile,
   log,
   newTransactionWithComputeUnitPriceAndLimit,
@@ -21,7 +24,14 @@ import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddressSync,
 } from "@solana/spl-token";
-import { ENDO_AVS_DEVNET, ENDO_AVS_PROGRAM_ID_DEVNET, ENDO_AVS_TOKEN_MINT_DEVNET, LRT_TEMPLATE_PROGRAM_ID_DEVNET, SOLAYER_SOL_MINT_PUB_KEY_DEVNET } from "./constants";
+import {
+  ENDO_AVS_DEVNET,
+  ENDO_AVS_PROGRAM_ID_DEVNET,
+  ENDO_AVS_TOKEN_MINT_DEVNET,
+  LRT_TEMPLATE_PROGRAM_ID_DEVNET,
+  SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+  OUTPUT_TOKEN_SYMBOL,
+} from "./constants";
 
 // signer keypair
 const KEYPAIR = Keypair.fromSecretKey(
@@ -38,9 +48,11 @@ const DELEGATE_AUTHORITY = loadKeypairFromFile(
   "./keys/delegate_authority.json"
 );
 
-// you can generate a new one for your use
-const OUTPUT_TOKEN_MINT_KEYPAIR = loadKeypairFromFile(
-  "./keys/output_token_mint.json"
+// created by initialize, derived from the input token mint, the admin and the symbol
+const OUTPUT_TOKEN_MINT = findOutputTokenMint(
+  SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+  KEYPAIR.publicKey,
+  OUTPUT_TOKEN_SYMBOL
 );
 
 const UNELEGATE_AMOUNT = 1;
@@ -61,7 +73,7 @@ async function main() {
   );
 
   const [pool, _] = PublicKey.findProgramAddressSync(
-    [Buffer.from("lrt_pool"), OUTPUT_TOKEN_MINT_KEYPAIR.publicKey.toBuffer()],
+    [Buffer.from("lrt_pool"), OUTPUT_TOKEN_MINT.toBuffer()],
     program.programId
   );
 
@@ -103,7 +115,14 @@ async function main() {
       poolInputTokenVault,
       poolAvsTokenVault,
       pool,
+      outputTokenMint: OUTPUT_TOKEN_MINT,
+      avsPoints: findAvsPoints(pool, ENDO_AVS_DEVNET),
       avsProgram: ENDO_AVS_PROGRAM_ID_DEVNET,
+      avsAllowlist: findAvsAllowlist(
+        pool,
+        ENDO_AVS_DEVNET,
+        ENDO_AVS_PROGRAM_ID_DEVNET
+      ),
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
@@ -12,11 +12,18 @@ import {
 } from "@solana/web3.js";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotentInstruction,
   getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   airdropSol,
+  findAvsAllowlist,
+  findAvsPoints,
+  findHolderCheckpoint,
+  findOutputTokenMint,
+  findUserPosition,
   loadKeypairFromFile,
   log,
   newTransactionWithComputeUnitPriceAndLimit,
@@ -28,6 +35,7 @@ import {
   ENDO_AVS_TOKEN_MINT_DEVNET,
   LRT_TEMPLATE_PROGRAM_ID_DEVNET,
   SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+  OUTPUT_TOKEN_SYMBOL,
 } from "./constants";
 
 // LST mint admin keypair
@@ -42,9 +50,11 @@ const KEYPAIR = Keypair.fromSecretKey(
 
 const USER_KEYPAIR = loadKeypairFromFile("./keys/user.json");
 
-// use the same one as initialize
-const OUTPUT_TOKEN_MINT_KEYPAIR = loadKeypairFromFile(
-  "./keys/output_token_mint.json"
+// created by initialize, derived from the input token mint, the admin and the symbol
+const OUTPUT_TOKEN_MINT = findOutputTokenMint(
+  SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+  KEYPAIR.publicKey,
+  OUTPUT_TOKEN_SYMBOL
 );
 
 const WITHDRAW_AMOUNT = 1;
@@ -68,7 +78,7 @@ async function main() {
   );
 
   const [pool, bump] = PublicKey.findProgramAddressSync(
-    [Buffer.from("lrt_pool"), OUTPUT_TOKEN_MINT_KEYPAIR.publicKey.toBuffer()],
+    [Buffer.from("lrt_pool"), OUTPUT_TOKEN_MINT.toBuffer()],
     program.programId
   );
 
@@ -97,24 +107,25 @@ async function main() {
   );
 
   const signerOutputTokenVault = getAssociatedTokenAddressSync(
-    OUTPUT_TOKEN_MINT_KEYPAIR.publicKey,
+    OUTPUT_TOKEN_MINT,
     USER_KEYPAIR.publicKey,
-    true
+    true,
+    TOKEN_2022_PROGRAM_ID
   );
 
   console.log(
     "output_token_mint: ",
-    OUTPUT_TOKEN_MINT_KEYPAIR.publicKey.toBase58()
+    OUTPUT_TOKEN_MINT.toBase58()
   );
   console.log("input_token_mint: ", SOLAYER_SOL_MINT_PUB_KEY_DEVNET.toBase58());
   console.log("signer_input_token_vault: ", signerInputTokenVault.toBase58());
   console.log("pool_input_token_vault: ", poolInputTokenVault.toBase58());
   console.log(
     "output_token_mint: ",
-    OUTPUT_TOKEN_MINT_KEYPAIR.publicKey.toBase58()
+    OUTPUT_TOKEN_MINT.toBase58()
   );
   console.log(
-    "signer_output_token_vault (init_if_needed): ",
+    "signer_output_token_vault: ",
     signerOutputTokenVault.toBase58()
   );
   console.log("pool and bump: ", pool.toBase58(), bump);
@@ -135,23 +146,44 @@ async function main() {
 
   let tx = newTransactionWithComputeUnitPriceAndLimit();
 
+  // the pool pays out to any input token account of the recipient, use its ATA here
+  tx.add(
+    createAssociatedTokenAccountIdempotentInstruction(
+      USER_KEYPAIR.publicKey,
+      signerInputTokenVault,
+      USER_KEYPAIR.publicKey,
+      SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+      TOKEN_PROGRAM_ID
+    )
+  );
+
   const withdrawInst = await program.methods
     .withdrawDelegatedStake(new anchor.BN(WITHDRAW_AMOUNT * LAMPORTS_PER_SOL))
     .accounts({
       signer: USER_KEYPAIR.publicKey,
+      recipient: USER_KEYPAIR.publicKey,
       inputTokenMint: SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
-      signerInputTokenVault,
+      recipientInputTokenVault: signerInputTokenVault,
       poolInputTokenVault,
-      outputTokenMint: OUTPUT_TOKEN_MINT_KEYPAIR.publicKey,
+      outputTokenMint: OUTPUT_TOKEN_MINT,
       signerOutputTokenVault,
       pool,
+      userPosition: findUserPosition(pool, USER_KEYPAIR.publicKey),
+      signerCheckpoint: findHolderCheckpoint(pool, signerOutputTokenVault),
       avs: ENDO_AVS_DEVNET,
       avsTokenMint: ENDO_AVS_TOKEN_MINT_DEVNET,
       avsInputTokenVault,
       poolAvsTokenVault,
+      avsPoints: findAvsPoints(pool, ENDO_AVS_DEVNET),
       avsProgram: ENDO_AVS_PROGRAM_ID_DEVNET,
+      avsAllowlist: findAvsAllowlist(
+        pool,
+        ENDO_AVS_DEVNET,
+        ENDO_AVS_PROGRAM_ID_DEVNET
+      ),
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-      tokenProgram: TOKEN_PROGRAM_ID,
+      inputTokenProgram: TOKEN_PROGRAM_ID,
+      outputTokenProgram: TOKEN_2022_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
     })
     .remainingAccounts([
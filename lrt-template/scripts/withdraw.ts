@@ -11,18 +11,26 @@ import {
   SystemProgram,
 } from "@solana/web3.js";
 import {
-  ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   airdropSol,
+  findHolderCheckpoint,
+  findOutputTokenMint,
+  findUserPosition,
   loadKeypairFromFile,
   log,
   newTransactionWithComputeUnitPriceAndLimit,
 } from "./helpers";
 import { assert } from "chai";
-import { LRT_TEMPLATE_PROGRAM_ID_DEVNET, SOLAYER_SOL_MINT_PUB_KEY_DEVNET, STAKED_SOL_MINT_PUB_KEY_DEVNET } from "./constants";
+import {
+  LRT_TEMPLATE_PROGRAM_ID_DEVNET,
+  SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+  STAKED_SOL_MINT_PUB_KEY_DEVNET,
+  OUTPUT_TOKEN_SYMBOL,
+} from "./constants";
 
 // LST mint admin keypair
 const KEYPAIR = Keypair.fromSecretKey(
@@ -36,8 +44,12 @@ const KEYPAIR = Keypair.fromSecretKey(
 
 const USER_KEYPAIR = loadKeypairFromFile("./keys/user.json");
 
-// use the same one as initialize
-const OUTPUT_TOKEN_MINT_KEYPAIR = loadKeypairFromFile("./keys/output_token_mint.json");
+// created by initialize, derived from the input token mint, the admin and the symbol
+const OUTPUT_TOKEN_MINT = findOutputTokenMint(
+  SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
+  KEYPAIR.publicKey,
+  OUTPUT_TOKEN_SYMBOL
+);
 
 const WITHDRAW_AMOUNT = 1;
 
@@ -62,7 +74,7 @@ async function main() {
   const [pool, bump] = PublicKey.findProgramAddressSync(
     [
       Buffer.from("lrt_pool"),
-      OUTPUT_TOKEN_MINT_KEYPAIR.publicKey.toBuffer(),
+      OUTPUT_TOKEN_MINT.toBuffer(),
     ],
     program.programId
   );
@@ -86,9 +98,10 @@ async function main() {
   );
 
   const signerOutputTokenVault = getAssociatedTokenAddressSync(
-    OUTPUT_TOKEN_MINT_KEYPAIR.publicKey,
+    OUTPUT_TOKEN_MINT,
     USER_KEYPAIR.publicKey,
-    true
+    true,
+    TOKEN_2022_PROGRAM_ID
   );
 
   console.log("input_token_mint: ", SOLAYER_SOL_MINT_PUB_KEY_DEVNET.toBase58());
@@ -96,7 +109,7 @@ async function main() {
   console.log("pool_input_token_vault: ", poolInputTokenVault.toBase58());
   console.log(
     "output_token_mint: ",
-    OUTPUT_TOKEN_MINT_KEYPAIR.publicKey.toBase58()
+    OUTPUT_TOKEN_MINT.toBase58()
   );
   console.log(
     "signer_output_token_vault (init_if_needed): ",
@@ -117,14 +130,17 @@ async function main() {
     .withdraw(new anchor.BN(WITHDRAW_AMOUNT * LAMPORTS_PER_SOL))
     .accounts({
       signer: USER_KEYPAIR.publicKey,
+      recipient: USER_KEYPAIR.publicKey,
       inputTokenMint: SOLAYER_SOL_MINT_PUB_KEY_DEVNET,
-      signerInputTokenVault,
+      recipientInputTokenVault: signerInputTokenVault,
       poolInputTokenVault,
-      outputTokenMint: OUTPUT_TOKEN_MINT_KEYPAIR.publicKey,
+      outputTokenMint: OUTPUT_TOKEN_MINT,
       signerOutputTokenVault,
       pool,
-      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-      tokenProgram: TOKEN_PROGRAM_ID,
+      userPosition: findUserPosition(pool, USER_KEYPAIR.publicKey),
+      signerCheckpoint: findHolderCheckpoint(pool, signerOutputTokenVault),
+      inputTokenProgram: TOKEN_PROGRAM_ID,
+      outputTokenProgram: TOKEN_2022_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
     })
     .instruction();
@@ -1,16 +1,167 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddressSync,
+  getMint,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
 import { LrtTemplate } from "../target/types/lrt_template";
+import {
+  findExtraAccountMetaList,
+  findOutputTokenMint,
+} from "../scripts/helpers";
+
+const OUTPUT_TOKEN_NAME = "LRT Test";
+const OUTPUT_TOKEN_SYMBOL = "LRTT";
+const OUTPUT_TOKEN_URI = "";
+
+// 1 output token for 1 input token, see LRTPool::exchange_rate
+const EXCHANGE_RATE_PRECISION = 1_000_000_000;
+
+const DEPOSIT_CAPS = {
+  depositCap: new anchor.BN(0),
+  userDepositCap: new anchor.BN(0),
+  rampInitialCap: new anchor.BN(0),
+  rampStart: new anchor.BN(0),
+  rampEnd: new anchor.BN(0),
+};
 
 describe("lrt-template", () => {
   // Configure the client to use the local cluster.
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
   const program = anchor.workspace.LrtTemplate as Program<LrtTemplate>;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const delegateAuthority = Keypair.generate();
+
+  function initialize(inputTokenMint: PublicKey) {
+    const outputTokenMint = findOutputTokenMint(
+      inputTokenMint,
+      payer.publicKey,
+      OUTPUT_TOKEN_SYMBOL,
+      program.programId
+    );
+    const [pool] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lrt_pool"), outputTokenMint.toBuffer()],
+      program.programId
+    );
+
+    const builder = program.methods
+      .initialize(
+        new anchor.BN(0),
+        OUTPUT_TOKEN_NAME,
+        OUTPUT_TOKEN_SYMBOL,
+        OUTPUT_TOKEN_URI,
+        0,
+        DEPOSIT_CAPS
+      )
+      .accounts({
+        signer: payer.publicKey,
+        delegateAuthority: delegateAuthority.publicKey,
+        admin: payer.publicKey,
+        guardian: payer.publicKey,
+        inputTokenMint,
+        poolInputTokenVault: getAssociatedTokenAddressSync(
+          inputTokenMint,
+          pool,
+          true
+        ),
+        outputTokenMint,
+        pool,
+        extraAccountMetaList: findExtraAccountMetaList(
+          outputTokenMint,
+          program.programId
+        ),
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        inputTokenProgram: TOKEN_PROGRAM_ID,
+        outputTokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([delegateAuthority]);
+    return { builder, pool, outputTokenMint };
+  }
 
   it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+    const inputTokenMint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      9
+    );
+    const { builder, pool, outputTokenMint } = initialize(inputTokenMint);
+    await builder.rpc();
+
+    const poolAccount = await program.account.lrtPool.fetch(pool);
+    assert.ok(poolAccount.inputTokenMint.equals(inputTokenMint));
+    assert.ok(poolAccount.outputTokenMint.equals(outputTokenMint));
+    assert.ok(
+      poolAccount.delegateAuthority.equals(delegateAuthority.publicKey)
+    );
+    assert.ok(poolAccount.admin.equals(payer.publicKey));
+    assert.equal(
+      poolAccount.lastExchangeRate.toNumber(),
+      EXCHANGE_RATE_PRECISION
+    );
+
+    const mint = await getMint(
+      provider.connection,
+      outputTokenMint,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.ok(mint.mintAuthority.equals(pool));
+    assert.equal(mint.decimals, 9);
+  });
+
+  it("Initializes when the output mint address already holds lamports", async () => {
+    const inputTokenMint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      9
+    );
+    const { builder, outputTokenMint } = initialize(inputTokenMint);
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: payer.publicKey,
+          toPubkey: outputTokenMint,
+          lamports: 1,
+        })
+      )
+    );
+    await builder.rpc();
+
+    const mint = await getMint(
+      provider.connection,
+      outputTokenMint,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.equal(mint.decimals, 9);
+  });
+
+  it("Rejects an input token mint with an unaccepted freeze authority", async () => {
+    const inputTokenMint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      payer.publicKey,
+      9
+    );
+    const error = await initialize(inputTokenMint)
+      .builder.rpc()
+      .then(
+        () => null,
+        (error) => error as anchor.AnchorError
+      );
+    assert.equal(error?.error.errorCode.code, "UnsafeInputTokenMint");
   });
 });
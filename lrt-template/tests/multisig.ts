@@ -0,0 +1,215 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { assert } from "chai";
+import { LrtTemplate } from "../target/types/lrt_template";
+
+describe("multisig", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.LrtTemplate as Program<LrtTemplate>;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  const owners = [payer, Keypair.generate(), Keypair.generate()];
+  const createKey = Keypair.generate();
+  const recipient = Keypair.generate().publicKey;
+  const TRANSFER_AMOUNT = 1_000_000;
+
+  const [multisig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("multisig"), createKey.publicKey.toBuffer()],
+    program.programId
+  );
+  const [multisigSigner] = PublicKey.findProgramAddressSync(
+    [Buffer.from("multisig_signer"), multisig.toBuffer()],
+    program.programId
+  );
+
+  function findProposal(id: number): PublicKey {
+    const [proposal] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("multisig_proposal"),
+        multisig.toBuffer(),
+        new anchor.BN(id).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    return proposal;
+  }
+
+  async function errorCode(promise: Promise<unknown>): Promise<string> {
+    return promise.then(
+      () => null,
+      (error) => (error as anchor.AnchorError).error?.errorCode.code
+    );
+  }
+
+  // the multisig signer pays recipient, it signs through the executed proposal
+  async function proposeTransfer(): Promise<PublicKey> {
+    const { proposalCount } = await program.account.multisig.fetch(multisig);
+    const proposal = findProposal(proposalCount.toNumber());
+    const inst = SystemProgram.transfer({
+      fromPubkey: multisigSigner,
+      toPubkey: recipient,
+      lamports: TRANSFER_AMOUNT,
+    });
+    await program.methods
+      .proposeTransaction(
+        inst.programId,
+        inst.keys.map((key) => ({
+          pubkey: key.pubkey,
+          isSigner: false,
+          isWritable: key.isWritable,
+        })),
+        inst.data
+      )
+      .accounts({
+        proposer: payer.publicKey,
+        multisig,
+        proposal,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return proposal;
+  }
+
+  function approve(proposal: PublicKey, owner: Keypair) {
+    return program.methods
+      .approveTransaction()
+      .accounts({ owner: owner.publicKey, multisig, proposal })
+      .signers([owner])
+      .rpc();
+  }
+
+  async function execute(proposal: PublicKey) {
+    const { programId, accounts } = await program.account.multisigProposal.fetch(
+      proposal
+    );
+    return program.methods
+      .executeTransaction()
+      .accounts({ multisig, multisigSigner, proposal })
+      .remainingAccounts([
+        ...accounts.map((account) => ({
+          pubkey: account.pubkey,
+          isSigner: false,
+          isWritable: account.isWritable,
+        })),
+        { pubkey: programId, isSigner: false, isWritable: false },
+      ])
+      .rpc();
+  }
+
+  before(async () => {
+    await program.methods
+      .createMultisig(
+        owners.map((owner) => owner.publicKey),
+        2
+      )
+      .accounts({
+        signer: payer.publicKey,
+        createKey: createKey.publicKey,
+        multisig,
+        multisigSigner,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([createKey])
+      .rpc();
+
+    const tx = new anchor.web3.Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: payer.publicKey,
+        toPubkey: multisigSigner,
+        lamports: LAMPORTS_PER_SOL,
+      }),
+      SystemProgram.transfer({
+        fromPubkey: payer.publicKey,
+        toPubkey: recipient,
+        lamports: LAMPORTS_PER_SOL,
+      })
+    );
+    await provider.sendAndConfirm(tx);
+  });
+
+  it("Executes a proposal once the threshold is reached", async () => {
+    const proposal = await proposeTransfer();
+
+    // proposing counts as one approval, the threshold is two
+    assert.equal(
+      await errorCode(execute(proposal)),
+      "NotEnoughMultisigApprovals"
+    );
+    assert.equal(
+      await errorCode(approve(proposal, Keypair.generate())),
+      "NotMultisigOwner"
+    );
+
+    await approve(proposal, owners[1]);
+    const balanceBefore = await provider.connection.getBalance(recipient);
+    await execute(proposal);
+    assert.equal(
+      await provider.connection.getBalance(recipient),
+      balanceBefore + TRANSFER_AMOUNT
+    );
+  });
+
+  it("Cannot replay an executed proposal", async () => {
+    const proposal = await proposeTransfer();
+    await approve(proposal, owners[2]);
+    await execute(proposal);
+
+    assert.equal(
+      await errorCode(execute(proposal)),
+      "MultisigProposalAlreadyExecuted"
+    );
+    assert.equal(
+      await errorCode(approve(proposal, owners[1])),
+      "MultisigProposalAlreadyExecuted"
+    );
+  });
+
+  it("Invalidates pending proposals when the owners change", async () => {
+    const staleProposal = await proposeTransfer();
+
+    // set_multisig_owners can only be signed by the multisig signer itself
+    const { proposalCount } = await program.account.multisig.fetch(multisig);
+    const setOwnersProposal = findProposal(proposalCount.toNumber());
+    await program.methods
+      .proposeTransaction(
+        program.programId,
+        [
+          { pubkey: multisig, isSigner: false, isWritable: true },
+          { pubkey: multisigSigner, isSigner: true, isWritable: false },
+        ],
+        program.coder.instruction.encode("setMultisigOwners", {
+          owners: owners.slice(0, 2).map((owner) => owner.publicKey),
+          threshold: 2,
+        })
+      )
+      .accounts({
+        proposer: payer.publicKey,
+        multisig,
+        proposal: setOwnersProposal,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await approve(setOwnersProposal, owners[1]);
+    await execute(setOwnersProposal);
+
+    const account = await program.account.multisig.fetch(multisig);
+    assert.equal(account.owners.length, 2);
+    assert.equal(account.ownerSetSeqno, 1);
+
+    assert.equal(
+      await errorCode(approve(staleProposal, owners[1])),
+      "StaleMultisigProposal"
+    );
+    assert.equal(
+      await errorCode(execute(staleProposal)),
+      "StaleMultisigProposal"
+    );
+  });
+});